@@ -64,10 +64,13 @@ fn main() {
     }
 
     let header_path = whisper_dir.join("include/whisper.h");
+    let backend_header_path = whisper_dir.join("ggml/include/ggml-backend.h");
     println!("cargo:rerun-if-changed={}", header_path.display());
+    println!("cargo:rerun-if-changed={}", backend_header_path.display());
 
     let bindings = bindgen::Builder::default()
         .header(header_path.to_string_lossy().into_owned())
+        .header(backend_header_path.to_string_lossy().into_owned())
         .clang_arg(format!("-I{}", whisper_dir.join("include").display()))
         .clang_arg(format!("-I{}", whisper_dir.join("ggml/include").display()))
         .allowlist_function("whisper_context_default_params")
@@ -78,11 +81,24 @@ fn main() {
         .allowlist_function("whisper_full_get_segment_text")
         .allowlist_function("whisper_free")
         .allowlist_function("whisper_log_set")
+        .allowlist_function("whisper_vad_default_context_params")
+        .allowlist_function("whisper_vad_init_from_file_with_params")
+        .allowlist_function("whisper_vad_detect_speech")
+        .allowlist_function("whisper_vad_n_probs")
+        .allowlist_function("whisper_vad_probs")
+        .allowlist_function("whisper_vad_free")
+        .allowlist_function("ggml_backend_dev_count")
+        .allowlist_function("ggml_backend_dev_get")
+        .allowlist_function("ggml_backend_dev_name")
+        .allowlist_function("ggml_backend_dev_type")
         .allowlist_type("whisper_context")
         .allowlist_type("whisper_context_params")
         .allowlist_type("whisper_full_params")
+        .allowlist_type("whisper_vad_context")
+        .allowlist_type("whisper_vad_context_params")
         .allowlist_type("ggml_log_callback")
         .allowlist_type("ggml_log_level")
+        .allowlist_type("ggml_backend_dev_type")
         .allowlist_type("whisper_sampling_strategy")
         .allowlist_var("whisper_sampling_strategy_.*")
         .generate()
@@ -1,20 +1,48 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use ringbuf::{HeapConsumer, HeapProducer, HeapRb};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
+use crate::resample::Resampler;
+use crate::types::{ChannelSelection, ResampleQuality};
+
 pub struct Capture {
     _stream: cpal::Stream,
     consumer: HeapConsumer<f32>,
-    overflow: Arc<Mutex<Vec<f32>>>,
     overflow_count: Arc<AtomicUsize>,
+    native_rate: u32,
+    sample_rate: u32,
+    resampler: Option<Resampler>,
+    native: Vec<f32>,
+    stream_error: Arc<OnceLock<cpal::StreamError>>,
+    device_label: String,
+    details: Vec<String>,
 }
 
 pub const DEFAULT_CHUNK_MS: u64 = 100;
 pub const DEFAULT_VAD_THRESHOLD: f32 = 0.010;
+pub const DEFAULT_VAD_PROBABILITY: f32 = 0.5;
+pub const DEFAULT_CALIBRATION_MS: u64 = 500;
+pub const NOISE_FLOOR_MULTIPLIER: f32 = 3.0;
+pub const MIN_VAD_THRESHOLD: f32 = 0.002;
+pub const MAX_VAD_THRESHOLD: f32 = 0.1;
+pub const DEFAULT_AUDIO_LEVEL_INTERVAL_MS: u64 = 100;
+pub const DEFAULT_PRE_ROLL_MS: u64 = 300;
+pub const CLIPPING_LEVEL: f32 = 0.99;
+const MIN_DBFS: f32 = -100.0;
+// Seconds of native-rate audio the capture ring can hold before samples are
+// dropped. The daemon drains it every 20 ms, so it only fills up while the
+// loop is busy (loading a model or transcribing with warm capture); a long
+// transcription must not cost the audio recorded meanwhile.
+const CAPTURE_RING_SECONDS: usize = 60;
+// Frames converted per step inside the stream callback; scratch buffers are
+// sized for this up front so the callback never allocates.
+const CALLBACK_BLOCK_FRAMES: usize = 1024;
 pub const DEFAULT_SILENCE_TIMEOUT_MS: u64 = 1200;
 
 pub struct VadConfig {
@@ -22,16 +50,98 @@ pub struct VadConfig {
     pub energy_threshold: f32,
     pub silence_timeout: Duration,
     pub chunk_size: Duration,
-    #[allow(dead_code)]
     pub debug: bool,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct VadChunk {
+    pub start: usize,
+    pub len: usize,
+    pub score: f32,
+    pub speech: bool,
+    pub frame_probabilities: Vec<f32>,
+}
+
+pub trait VoiceActivityDetector {
+    fn name(&self) -> &'static str;
+    fn analyze(
+        &mut self,
+        samples: &[f32],
+        chunk_samples: usize,
+    ) -> Result<Vec<VadChunk>, AudioError>;
+
+    // Only meaningful for threshold-based detectors.
+    fn calibrate(&mut self, _calibration: &NoiseCalibration) {}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NoiseCalibration {
+    pub noise_floor: f32,
+    pub threshold: f32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AudioLevel {
+    pub rms_dbfs: f32,
+    pub peak_dbfs: f32,
+    pub clipping: bool,
+}
+
+// Keeps the most recent samples captured while idle so they can be prepended
+// to the next recording.
+pub struct PreRollBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+}
+
+pub struct EnergyVad {
+    threshold: f32,
+}
+
+pub struct SilenceTracker {
+    sample_rate: u32,
+    chunk_samples: usize,
+    silence_timeout_ms: u64,
+    position: usize,
+    speech_detected: bool,
+    silence_ms: u64,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct SegmentInfo {
     pub index: u64,
     pub duration_ms: u64,
 }
 
+// Rates probed against each supported config range when describing a device.
+const COMMON_SAMPLE_RATES: &[u32] = &[
+    8_000, 11_025, 16_000, 22_050, 24_000, 32_000, 44_100, 48_000, 88_200, 96_000, 192_000,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputDeviceInfo {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+    pub sample_rates: Vec<u32>,
+    pub channels: Vec<u16>,
+    pub sample_formats: Vec<String>,
+}
+
+impl InputDeviceInfo {
+    pub fn named(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            id: name.clone(),
+            name,
+            is_default: false,
+            sample_rates: Vec::new(),
+            channels: Vec::new(),
+            sample_formats: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum AudioErrorKind {
     DeviceNotFound,
@@ -40,6 +150,7 @@ pub enum AudioErrorKind {
     StreamConfig,
     StreamBuild,
     StreamStart,
+    Vad,
 }
 
 #[derive(Debug)]
@@ -49,7 +160,7 @@ pub struct AudioError {
 }
 
 impl AudioError {
-    fn new(kind: AudioErrorKind, message: impl Into<String>) -> Self {
+    pub fn new(kind: AudioErrorKind, message: impl Into<String>) -> Self {
         Self {
             kind,
             message: message.into(),
@@ -81,6 +192,123 @@ impl VadConfig {
             debug,
         }
     }
+
+    pub fn chunk_samples(&self, sample_rate: u32) -> usize {
+        duration_to_samples(sample_rate, self.chunk_size).max(1)
+    }
+}
+
+impl PreRollBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        let keep = &samples[samples.len().saturating_sub(self.capacity)..];
+        let overflow = (self.samples.len() + keep.len()).saturating_sub(self.capacity);
+        self.samples.drain(..overflow);
+        self.samples.extend(keep);
+    }
+
+    pub fn take(&mut self) -> Vec<f32> {
+        self.samples.drain(..).collect()
+    }
+}
+
+impl EnergyVad {
+    pub fn new(threshold: f32) -> Self {
+        Self { threshold }
+    }
+}
+
+impl VoiceActivityDetector for EnergyVad {
+    fn name(&self) -> &'static str {
+        "energy"
+    }
+
+    fn analyze(
+        &mut self,
+        samples: &[f32],
+        chunk_samples: usize,
+    ) -> Result<Vec<VadChunk>, AudioError> {
+        let chunk_samples = chunk_samples.max(1);
+        Ok(samples
+            .chunks(chunk_samples)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let score = rms_energy(chunk);
+                VadChunk {
+                    start: index * chunk_samples,
+                    len: chunk.len(),
+                    score,
+                    speech: score >= self.threshold,
+                    frame_probabilities: Vec::new(),
+                }
+            })
+            .collect())
+    }
+
+    fn calibrate(&mut self, calibration: &NoiseCalibration) {
+        self.threshold = calibration.threshold;
+    }
+}
+
+impl SilenceTracker {
+    pub fn new(sample_rate: u32, vad: &VadConfig) -> Self {
+        Self {
+            sample_rate,
+            chunk_samples: vad.chunk_samples(sample_rate),
+            silence_timeout_ms: vad.silence_timeout.as_millis() as u64,
+            position: 0,
+            speech_detected: false,
+            silence_ms: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.speech_detected = false;
+        self.silence_ms = 0;
+    }
+
+    pub fn speech_detected(&self) -> bool {
+        self.speech_detected
+    }
+
+    // Consumes the complete chunks appended to `samples` since the last call and
+    // reports whether detected speech has been followed by the silence timeout.
+    pub fn update(
+        &mut self,
+        samples: &[f32],
+        detector: &mut dyn VoiceActivityDetector,
+    ) -> Result<bool, AudioError> {
+        if samples.len() < self.position {
+            self.reset();
+        }
+
+        let complete = (samples.len() - self.position) / self.chunk_samples * self.chunk_samples;
+        if complete > 0 {
+            let end = self.position + complete;
+            let chunks = detector.analyze(&samples[self.position..end], self.chunk_samples)?;
+            for chunk in chunks {
+                if chunk.speech {
+                    self.speech_detected = true;
+                    self.silence_ms = 0;
+                } else if self.speech_detected {
+                    self.silence_ms += samples_to_ms(chunk.len, self.sample_rate);
+                }
+            }
+            self.position = end;
+        }
+
+        Ok(self.speech_detected && self.silence_ms >= self.silence_timeout_ms)
+    }
 }
 
 pub fn list_input_devices(host: &cpal::Host) -> Result<Vec<String>, AudioError> {
@@ -112,6 +340,201 @@ pub fn list_input_devices(host: &cpal::Host) -> Result<Vec<String>, AudioError>
     Ok(names)
 }
 
+// Device ids are "<host>:<device name>". cpal names come from the backend's
+// own identifiers (ALSA PCM names such as `hw:CARD=Mic,DEV=0`), so they stay
+// the same across reboots and enumeration order changes.
+pub fn describe_input_devices(host: &cpal::Host) -> Result<Vec<InputDeviceInfo>, AudioError> {
+    let devices = host.input_devices().map_err(|err| {
+        AudioError::new(
+            AudioErrorKind::DeviceQuery,
+            format!("failed to list input devices: {err}"),
+        )
+    })?;
+    let default_name = host
+        .default_input_device()
+        .and_then(|device| device.name().ok());
+    let host_name = host_name(host);
+
+    let mut infos = Vec::new();
+    for device in devices {
+        let name = device.name().map_err(|err| {
+            AudioError::new(
+                AudioErrorKind::DeviceQuery,
+                format!("failed to read device name: {err}"),
+            )
+        })?;
+        let mut info = InputDeviceInfo::named(name.clone());
+        info.id = format!("{host_name}:{name}");
+        info.is_default = default_name.as_deref() == Some(name.as_str());
+        // Devices that are busy or half-gone fail to report configs; list them anyway.
+        if let Ok(configs) = device.supported_input_configs() {
+            for config in configs {
+                let min = config.min_sample_rate().0;
+                let max = config.max_sample_rate().0;
+                info.sample_rates.extend(
+                    COMMON_SAMPLE_RATES
+                        .iter()
+                        .filter(|rate| (min..=max).contains(*rate)),
+                );
+                info.channels.push(config.channels());
+                info.sample_formats
+                    .push(format!("{:?}", config.sample_format()).to_lowercase());
+            }
+        }
+        info.sample_rates.sort_unstable();
+        info.sample_rates.dedup();
+        info.channels.sort_unstable();
+        info.channels.dedup();
+        info.sample_formats.sort();
+        info.sample_formats.dedup();
+        infos.push(info);
+    }
+
+    if infos.is_empty() {
+        return Err(AudioError::new(
+            AudioErrorKind::DeviceUnavailable,
+            "no input devices available",
+        ));
+    }
+    Ok(infos)
+}
+
+// Resolves a configured device against the names listed on `host`: an exact
+// (case-insensitive) name first, then a `<host>:<name>` id for this host, then
+// a unique substring match ignoring case, spaces and punctuation.
+pub fn find_device(host: &str, names: &[String], requested: &str) -> Result<usize, AudioError> {
+    if let Some(index) = names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(requested))
+    {
+        return Ok(index);
+    }
+    if let Some((id_host, name)) = requested.split_once(':') {
+        if id_host.eq_ignore_ascii_case(host) {
+            if let Some(index) = names
+                .iter()
+                .position(|candidate| candidate.eq_ignore_ascii_case(name))
+            {
+                return Ok(index);
+            }
+        }
+    }
+
+    let needle = normalize_device_name(requested);
+    let matches: Vec<usize> = if needle.is_empty() {
+        Vec::new()
+    } else {
+        names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| normalize_device_name(name).contains(&needle))
+            .map(|(index, _)| index)
+            .collect()
+    };
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(AudioError::new(
+            AudioErrorKind::DeviceNotFound,
+            format!("input device not found: {requested}"),
+        )),
+        _ => Err(AudioError::new(
+            AudioErrorKind::DeviceNotFound,
+            format!(
+                "input device '{requested}' is ambiguous; matches: {}",
+                matches
+                    .iter()
+                    .map(|index| names[*index].as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )),
+    }
+}
+
+fn normalize_device_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Folds interleaved frames into the mono stream the pipeline works on.
+// `MaxEnergy` follows the loudest channel using a smoothed per-channel energy
+// so it does not flip between channels on every buffer.
+pub struct ChannelMixer {
+    selection: ChannelSelection,
+    channels: usize,
+    energy: Vec<f32>,
+}
+
+const CHANNEL_ENERGY_SMOOTHING: f32 = 0.8;
+
+impl ChannelMixer {
+    pub fn new(selection: ChannelSelection, channels: u16) -> Result<Self, AudioError> {
+        if let ChannelSelection::Index(index) = selection {
+            if index > channels {
+                return Err(AudioError::new(
+                    AudioErrorKind::StreamConfig,
+                    format!("channel {index} not available; input has {channels} channel(s)"),
+                ));
+            }
+        }
+        let channels = channels.max(1) as usize;
+        Ok(Self {
+            selection,
+            channels,
+            energy: vec![0.0; channels],
+        })
+    }
+
+    pub fn mix(&mut self, interleaved: &[f32], output: &mut Vec<f32>) {
+        let channels = self.channels;
+        if channels == 1 {
+            output.extend_from_slice(interleaved);
+            return;
+        }
+        let frames = interleaved.chunks_exact(channels);
+        match self.selection {
+            ChannelSelection::Average => {
+                output.extend(frames.map(|frame| frame.iter().sum::<f32>() / channels as f32))
+            }
+            ChannelSelection::Index(index) => {
+                let index = usize::from(index) - 1;
+                output.extend(frames.map(|frame| frame[index]));
+            }
+            ChannelSelection::MaxEnergy => {
+                let frame_count = interleaved.len() / channels;
+                if frame_count == 0 {
+                    return;
+                }
+                for (channel, energy) in self.energy.iter_mut().enumerate() {
+                    let block = interleaved[..frame_count * channels]
+                        .iter()
+                        .skip(channel)
+                        .step_by(channels)
+                        .map(|sample| sample * sample)
+                        .sum::<f32>()
+                        / frame_count as f32;
+                    *energy = *energy * CHANNEL_ENERGY_SMOOTHING
+                        + block * (1.0 - CHANNEL_ENERGY_SMOOTHING);
+                }
+                let loudest = self
+                    .energy
+                    .iter()
+                    .enumerate()
+                    .fold(0, |best, (channel, energy)| {
+                        if *energy > self.energy[best] {
+                            channel
+                        } else {
+                            best
+                        }
+                    });
+                output.extend(frames.map(|frame| frame[loudest]));
+            }
+        }
+    }
+}
+
 pub fn configure_alsa_logging(debug_audio: bool) {
     #[cfg(target_os = "linux")]
     {
@@ -152,6 +575,8 @@ pub fn start_capture(
     host: &cpal::Host,
     device_name: Option<&str>,
     sample_rate: u32,
+    resample_quality: ResampleQuality,
+    channel: ChannelSelection,
 ) -> Result<Capture, AudioError> {
     let device = select_input_device(host, device_name)?;
     let device_label = device.name().map_err(|err| {
@@ -160,76 +585,57 @@ pub fn start_capture(
             format!("failed to read input device name: {err}"),
         )
     })?;
-    println!("Selected input device: {device_label}");
+    let mut details = vec![format!("Selected input device: {device_label}")];
 
-    let (stream_config, sample_format) = select_stream_config(&device, sample_rate)?;
-    println!(
+    let (stream_config, sample_format) = select_stream_config(&device, sample_rate, channel)?;
+    let native_rate = stream_config.sample_rate.0;
+    details.push(format!(
         "Input stream: {:?}, {} ch, {} Hz",
-        sample_format, stream_config.channels, stream_config.sample_rate.0
-    );
+        sample_format, stream_config.channels, native_rate
+    ));
+    let mixer = ChannelMixer::new(channel, stream_config.channels)?;
+    if stream_config.channels > 1 {
+        details.push(format!("Input channel: {channel}"));
+    }
+    let resampler = if native_rate == sample_rate {
+        None
+    } else {
+        details.push(format!(
+            "Resampling {native_rate} Hz -> {sample_rate} Hz ({resample_quality:?})"
+        ));
+        Some(Resampler::new(native_rate, sample_rate, resample_quality))
+    };
 
-    let ring = HeapRb::<f32>::new(sample_rate as usize * 30);
-    let (producer, consumer) = ring.split();
-    let overflow = Arc::new(Mutex::new(Vec::new()));
+    let (producer, consumer) = capture_ring(native_rate).split();
     let overflow_count = Arc::new(AtomicUsize::new(0));
+    let writer = CaptureWriter::new(producer, mixer, Arc::clone(&overflow_count));
+    let stream_error = Arc::new(OnceLock::new());
 
     let stream = match sample_format {
-        cpal::SampleFormat::F32 => build_input_stream::<f32>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::F64 => build_input_stream::<f64>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::I8 => build_input_stream::<i8>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::I16 => build_input_stream::<i16>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::I32 => build_input_stream::<i32>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::U8 => build_input_stream::<u8>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::U16 => build_input_stream::<u16>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
-        cpal::SampleFormat::U32 => build_input_stream::<u32>(
-            &device,
-            &stream_config,
-            producer,
-            &overflow,
-            &overflow_count,
-        )?,
+        cpal::SampleFormat::F32 => {
+            build_input_stream::<f32>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::F64 => {
+            build_input_stream::<f64>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::I8 => {
+            build_input_stream::<i8>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::I16 => {
+            build_input_stream::<i16>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::I32 => {
+            build_input_stream::<i32>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::U8 => {
+            build_input_stream::<u8>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::U16 => {
+            build_input_stream::<u16>(&device, &stream_config, writer, &stream_error)?
+        }
+        cpal::SampleFormat::U32 => {
+            build_input_stream::<u32>(&device, &stream_config, writer, &stream_error)?
+        }
         format => {
             return Err(AudioError::new(
                 AudioErrorKind::StreamConfig,
@@ -248,46 +654,195 @@ pub fn start_capture(
     Ok(Capture {
         _stream: stream,
         consumer,
-        overflow,
         overflow_count,
+        native_rate,
+        sample_rate,
+        resampler,
+        native: Vec::new(),
+        stream_error,
+        device_label,
+        details,
     })
 }
 
+pub fn stream_error(capture: &Capture) -> Option<String> {
+    capture.stream_error.get().map(ToString::to_string)
+}
+
+// What `start_capture` opened, for the caller to log.
+pub fn capture_details(capture: &Capture) -> &[String] {
+    &capture.details
+}
+
+pub fn capture_device(capture: &Capture) -> &str {
+    &capture.device_label
+}
+
 pub fn drain_samples(capture: &mut Capture, output: &mut Vec<f32>) {
-    while let Some(sample) = capture.consumer.pop() {
-        output.push(sample);
+    let native = if capture.resampler.is_some() {
+        capture.native.clear();
+        &mut capture.native
+    } else {
+        &mut *output
+    };
+    pop_available(&mut capture.consumer, native);
+    if let Some(resampler) = capture.resampler.as_mut() {
+        resampler.process(&capture.native, output);
+    }
+}
+
+// Returns how many samples (at the output sample rate) the stream callback had
+// to drop because the ring was full since the last call.
+pub fn take_overflow(capture: &Capture) -> usize {
+    let dropped = capture.overflow_count.swap(0, Ordering::Relaxed);
+    (dropped as u64 * u64::from(capture.sample_rate) / u64::from(capture.native_rate.max(1)))
+        as usize
+}
+
+// The lock-free queue between a capture callback and the daemon loop.
+pub(crate) fn capture_ring(native_rate: u32) -> HeapRb<f32> {
+    HeapRb::new(native_rate as usize * CAPTURE_RING_SECONDS)
+}
+
+pub(crate) fn pop_available(consumer: &mut HeapConsumer<f32>, output: &mut Vec<f32>) {
+    let start = output.len();
+    output.resize(start + consumer.len(), 0.0);
+    let popped = consumer.pop_slice(&mut output[start..]);
+    output.truncate(start + popped);
+}
+
+pub fn analyze_speech(
+    samples: &[f32],
+    sample_rate: u32,
+    vad: &VadConfig,
+    detector: &mut dyn VoiceActivityDetector,
+) -> Result<Vec<VadChunk>, AudioError> {
+    if samples.is_empty() {
+        return Ok(Vec::new());
+    }
+    detector.analyze(samples, vad.chunk_samples(sample_rate))
+}
+
+pub fn measure_level(samples: &[f32]) -> AudioLevel {
+    let peak = samples
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    AudioLevel {
+        rms_dbfs: to_dbfs(rms_energy(samples)),
+        peak_dbfs: to_dbfs(peak),
+        clipping: peak >= CLIPPING_LEVEL,
+    }
+}
+
+fn to_dbfs(level: f32) -> f32 {
+    if level <= 0.0 {
+        return MIN_DBFS;
+    }
+    (20.0 * level.log10()).max(MIN_DBFS)
+}
+
+// Uses the median chunk RMS so a short burst (a click or an early word) does not
+// drag the estimated floor up.
+pub fn calibrate_noise_floor(
+    samples: &[f32],
+    sample_rate: u32,
+    vad: &VadConfig,
+) -> Option<NoiseCalibration> {
+    let levels: Vec<f32> = samples
+        .chunks(vad.chunk_samples(sample_rate))
+        .map(rms_energy)
+        .collect();
+    noise_calibration(levels)
+}
+
+// Runtime variant: only chunks the detector classifies as silence count, so
+// pre-roll speech does not inflate the floor. When every chunk clears the current
+// threshold the floor itself sits above it, and the quietest chunk stands in.
+pub fn calibrate_from_silence(
+    samples: &[f32],
+    sample_rate: u32,
+    vad: &VadConfig,
+    detector: &mut dyn VoiceActivityDetector,
+) -> Result<Option<NoiseCalibration>, AudioError> {
+    let chunks = analyze_speech(samples, sample_rate, vad, detector)?;
+    let level = |chunk: &VadChunk| rms_energy(&samples[chunk.start..chunk.start + chunk.len]);
+    let silent: Vec<f32> = chunks
+        .iter()
+        .filter(|chunk| !chunk.speech)
+        .map(level)
+        .collect();
+    if !silent.is_empty() {
+        return Ok(noise_calibration(silent));
     }
-    let mut overflow = capture
-        .overflow
-        .lock()
-        .expect("overflow buffer lock poisoned");
-    if !overflow.is_empty() {
-        let drained = overflow.len();
-        output.extend(overflow.drain(..));
-        capture.overflow_count.store(0, Ordering::Release);
-        eprintln!("audio overflow drained: {drained} samples");
+    let quietest = chunks.iter().map(level).min_by(f32::total_cmp);
+    Ok(noise_calibration(quietest.into_iter().collect()))
+}
+
+fn noise_calibration(mut levels: Vec<f32>) -> Option<NoiseCalibration> {
+    if levels.is_empty() {
+        return None;
     }
+    levels.sort_by(f32::total_cmp);
+    let noise_floor = levels[levels.len() / 2];
+    Some(NoiseCalibration {
+        noise_floor,
+        threshold: (noise_floor * NOISE_FLOOR_MULTIPLIER)
+            .clamp(MIN_VAD_THRESHOLD, MAX_VAD_THRESHOLD),
+    })
+}
+
+pub fn chunks_from_frame_probabilities(
+    total_samples: usize,
+    chunk_samples: usize,
+    frame_samples: usize,
+    probabilities: &[f32],
+    threshold: f32,
+) -> Vec<VadChunk> {
+    let chunk_samples = chunk_samples.max(1);
+    let frame_samples = frame_samples.max(1);
+    (0..total_samples)
+        .step_by(chunk_samples)
+        .map(|start| {
+            let len = chunk_samples.min(total_samples - start);
+            let first_frame = start / frame_samples;
+            let last_frame = (start + len - 1) / frame_samples;
+            let frame_probabilities = probabilities
+                .get(first_frame..=last_frame.min(probabilities.len().saturating_sub(1)))
+                .unwrap_or_default()
+                .to_vec();
+            let score = frame_probabilities.iter().copied().fold(0.0f32, f32::max);
+            VadChunk {
+                start,
+                len,
+                score,
+                speech: score >= threshold,
+                frame_probabilities,
+            }
+        })
+        .collect()
 }
 
-pub fn trim_trailing_silence(samples: &[f32], sample_rate: u32, vad: &VadConfig) -> Vec<f32> {
+pub fn trim_trailing_silence(
+    samples: &[f32],
+    sample_rate: u32,
+    vad: &VadConfig,
+    chunks: &[VadChunk],
+) -> Vec<f32> {
     if samples.is_empty() || !vad.enabled {
         return samples.to_vec();
     }
 
-    let chunk_samples = duration_to_samples(sample_rate, vad.chunk_size).max(1);
     let max_silence_ms = vad.silence_timeout.as_millis() as u64;
     let mut silence_ms = 0u64;
     let mut end = samples.len();
 
-    while end > 0 {
-        let start = end.saturating_sub(chunk_samples);
-        let chunk = &samples[start..end];
-        let rms = rms_energy(chunk);
-        if rms >= vad.energy_threshold {
+    let len = samples.len();
+    for chunk in chunks.iter().rev().filter(|chunk| chunk.start < len) {
+        if chunk.speech {
             break;
         }
-        silence_ms += samples_to_ms(chunk.len(), sample_rate);
-        end = start;
+        silence_ms += samples_to_ms(end - chunk.start, sample_rate);
+        end = chunk.start;
         if silence_ms >= max_silence_ms {
             break;
         }
@@ -296,6 +851,26 @@ pub fn trim_trailing_silence(samples: &[f32], sample_rate: u32, vad: &VadConfig)
     samples[..end].to_vec()
 }
 
+pub fn trim_leading_silence(samples: &[f32], vad: &VadConfig, chunks: &[VadChunk]) -> Vec<f32> {
+    if samples.is_empty() || !vad.enabled {
+        return samples.to_vec();
+    }
+
+    let Some(first_speech) = chunks.iter().position(|chunk| chunk.speech) else {
+        return Vec::new();
+    };
+
+    // Keep one chunk of lead-in so soft word onsets are not clipped.
+    let start = chunks[first_speech.saturating_sub(1)]
+        .start
+        .min(samples.len());
+    samples[start..].to_vec()
+}
+
+pub fn contains_speech(chunks: &[VadChunk]) -> bool {
+    chunks.iter().any(|chunk| chunk.speech)
+}
+
 fn rms_energy(samples: &[f32]) -> f32 {
     let sum_squares = samples.iter().map(|sample| sample * sample).sum::<f32>();
     (sum_squares / samples.len() as f32).sqrt()
@@ -306,6 +881,15 @@ fn duration_to_samples(sample_rate: u32, duration: Duration) -> usize {
     (sample_rate as f32 * seconds).round() as usize
 }
 
+pub fn ms_to_samples(ms: u64, sample_rate: u32) -> usize {
+    (sample_rate as u64 * ms / 1000) as usize
+}
+
+// The `<host>` part of device ids.
+pub fn host_name(host: &cpal::Host) -> String {
+    host.id().name().to_lowercase()
+}
+
 pub fn samples_to_ms(samples: usize, sample_rate: u32) -> u64 {
     if sample_rate == 0 {
         return 0;
@@ -315,7 +899,6 @@ pub fn samples_to_ms(samples: usize, sample_rate: u32) -> u64 {
 
 fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::Device, AudioError> {
     if let Some(target) = name {
-        let target_lower = target.to_lowercase();
         let devices = host.input_devices().map_err(|err| {
             AudioError::new(
                 AudioErrorKind::DeviceQuery,
@@ -323,6 +906,8 @@ fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::De
             )
         })?;
 
+        let mut candidates = Vec::new();
+        let mut names = Vec::new();
         for device in devices {
             let device_name = device.name().map_err(|err| {
                 AudioError::new(
@@ -330,15 +915,12 @@ fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::De
                     format!("failed to read device name: {err}"),
                 )
             })?;
-            if device_name.to_lowercase() == target_lower {
-                return Ok(device);
-            }
+            names.push(device_name);
+            candidates.push(device);
         }
 
-        return Err(AudioError::new(
-            AudioErrorKind::DeviceNotFound,
-            format!("input device not found: {target}"),
-        ));
+        let index = find_device(&host_name(host), &names, target)?;
+        return Ok(candidates.swap_remove(index));
     }
 
     host.default_input_device().ok_or_else(|| {
@@ -352,6 +934,7 @@ fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::De
 fn select_stream_config(
     device: &cpal::Device,
     sample_rate: u32,
+    channel: ChannelSelection,
 ) -> Result<(cpal::StreamConfig, cpal::SampleFormat), AudioError> {
     let configs = device.supported_input_configs().map_err(|err| {
         AudioError::new(
@@ -360,32 +943,36 @@ fn select_stream_config(
         )
     })?;
 
+    // Prefer configs that expose the selected channel and run at the requested
+    // rate; otherwise capture at the closest native rate and resample.
+    let required_channels = match channel {
+        ChannelSelection::Index(index) => index,
+        ChannelSelection::Average | ChannelSelection::MaxEnergy => 1,
+    };
     let mut best: Option<(cpal::StreamConfig, cpal::SampleFormat)> = None;
-    let mut best_rank = 0u8;
+    let mut best_rank = (false, false, 0u32, 0u8);
     for config in configs {
-        let min_rate = config.min_sample_rate().0;
-        let max_rate = config.max_sample_rate().0;
-        if sample_rate < min_rate || sample_rate > max_rate {
-            continue;
-        }
-
+        let rate = native_rate_for(
+            config.min_sample_rate().0,
+            config.max_sample_rate().0,
+            sample_rate,
+        );
         let channels = config.channels();
         let stream_config = cpal::StreamConfig {
             channels,
-            sample_rate: cpal::SampleRate(sample_rate),
+            sample_rate: cpal::SampleRate(rate),
             buffer_size: cpal::BufferSize::Default,
         };
 
         let format = config.sample_format();
-        let candidate = (stream_config, format);
-        let rank = sample_format_rank(format);
-        if channels == 1 {
-            if best.is_none() || rank > best_rank {
-                best = Some(candidate);
-                best_rank = rank;
-            }
-        } else if best.is_none() || rank > best_rank {
-            best = Some(candidate);
+        let rank = (
+            channels >= required_channels,
+            rate == sample_rate,
+            u32::MAX - rate.abs_diff(sample_rate),
+            sample_format_rank(format),
+        );
+        if best.is_none() || rank > best_rank {
+            best = Some((stream_config, format));
             best_rank = rank;
         }
     }
@@ -393,11 +980,15 @@ fn select_stream_config(
     best.ok_or_else(|| {
         AudioError::new(
             AudioErrorKind::StreamConfig,
-            format!("no input config supports {sample_rate} Hz"),
+            "device reported no input configs",
         )
     })
 }
 
+fn native_rate_for(min_rate: u32, max_rate: u32, target: u32) -> u32 {
+    target.clamp(min_rate, max_rate.max(min_rate))
+}
+
 fn sample_format_rank(format: cpal::SampleFormat) -> u8 {
     match format {
         cpal::SampleFormat::F32 => 6,
@@ -411,65 +1002,73 @@ fn sample_format_rank(format: cpal::SampleFormat) -> u8 {
     }
 }
 
+// Runs inside the real-time stream callback: converts, mixes and pushes audio
+// into the lock-free ring without locking, allocating or logging. Samples
+// that do not fit are dropped and counted for the daemon to report.
+pub(crate) struct CaptureWriter {
+    producer: HeapProducer<f32>,
+    mixer: ChannelMixer,
+    overflow_count: Arc<AtomicUsize>,
+    interleaved: Vec<f32>,
+    mono: Vec<f32>,
+}
+
+impl CaptureWriter {
+    pub(crate) fn new(
+        producer: HeapProducer<f32>,
+        mixer: ChannelMixer,
+        overflow_count: Arc<AtomicUsize>,
+    ) -> Self {
+        let block_samples = CALLBACK_BLOCK_FRAMES * mixer.channels;
+        Self {
+            producer,
+            mixer,
+            overflow_count,
+            interleaved: Vec::with_capacity(block_samples),
+            mono: Vec::with_capacity(CALLBACK_BLOCK_FRAMES),
+        }
+    }
+
+    pub(crate) fn write<T>(&mut self, data: &[T])
+    where
+        T: cpal::Sample,
+        f32: cpal::FromSample<T>,
+    {
+        let block_samples = CALLBACK_BLOCK_FRAMES * self.mixer.channels;
+        for block in data.chunks(block_samples) {
+            self.interleaved.clear();
+            self.interleaved
+                .extend(block.iter().map(|sample| sample.to_sample::<f32>()));
+            self.mono.clear();
+            self.mixer.mix(&self.interleaved, &mut self.mono);
+            let pushed = self.producer.push_slice(&self.mono);
+            if pushed < self.mono.len() {
+                self.overflow_count
+                    .fetch_add(self.mono.len() - pushed, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
 fn build_input_stream<T>(
     device: &cpal::Device,
     config: &cpal::StreamConfig,
-    mut producer: HeapProducer<f32>,
-    overflow: &Arc<Mutex<Vec<f32>>>,
-    overflow_count: &Arc<AtomicUsize>,
+    mut writer: CaptureWriter,
+    stream_error: &Arc<OnceLock<cpal::StreamError>>,
 ) -> Result<cpal::Stream, AudioError>
 where
     T: cpal::Sample + cpal::SizedSample,
     f32: cpal::FromSample<T>,
 {
-    let channels = config.channels as usize;
-    let overflow = Arc::clone(overflow);
-    let overflow_count = Arc::clone(overflow_count);
-    let mut overflow_scratch = Vec::new();
+    let stream_error = Arc::clone(stream_error);
     device
         .build_input_stream(
             config,
-            move |data: &[T], _| {
-                overflow_scratch.clear();
-                let mut force_overflow = overflow_count.load(Ordering::Acquire) > 0;
-                if channels == 1 {
-                    for sample in data {
-                        let value = sample.to_sample::<f32>();
-                        if force_overflow || producer.push(value).is_err() {
-                            overflow_scratch.push(value);
-                            force_overflow = true;
-                        }
-                    }
-                } else {
-                    for frame in data.chunks(channels) {
-                        if frame.is_empty() {
-                            continue;
-                        }
-                        let mut sum = 0.0f32;
-                        for sample in frame {
-                            sum += sample.to_sample::<f32>();
-                        }
-                        let mono = sum / frame.len() as f32;
-                        if force_overflow || producer.push(mono).is_err() {
-                            overflow_scratch.push(mono);
-                            force_overflow = true;
-                        }
-                    }
-                }
-
-                if !overflow_scratch.is_empty() {
-                    let overflow_len = overflow_scratch.len();
-                    let was_empty = overflow_count.load(Ordering::Acquire) == 0;
-                    let mut overflow = overflow.lock().expect("overflow buffer lock poisoned");
-                    overflow.extend(overflow_scratch.drain(..));
-                    overflow_count.fetch_add(overflow_len, Ordering::Release);
-                    if was_empty {
-                        eprintln!("audio overflow detected: queued {overflow_len} samples");
-                    }
-                }
-            },
+            move |data: &[T], _| writer.write(data),
             move |err| {
-                eprintln!("audio input error: {err}");
+                // cpal stream errors are not recoverable; the stream has to be rebuilt.
+                // Only the first one is kept, and the daemon loop reports it.
+                let _ = stream_error.set(err);
             },
             None,
         )
@@ -480,3 +1079,323 @@ where
             )
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_ring_keeps_a_long_undrained_recording() {
+        let native_rate = 48_000;
+        let (producer, mut consumer) = capture_ring(native_rate).split();
+        let overflow_count = Arc::new(AtomicUsize::new(0));
+        let mixer = ChannelMixer::new(ChannelSelection::Average, 2).expect("stereo mixer");
+        let mut writer = CaptureWriter::new(producer, mixer, Arc::clone(&overflow_count));
+
+        // 45 s of stereo in 10 ms callbacks without the daemon draining once.
+        let seconds = 45;
+        let block = (0..native_rate as usize / 100)
+            .flat_map(|frame| [frame as f32 / 1_000.0, 0.0])
+            .collect::<Vec<_>>();
+        for _ in 0..seconds * 100 {
+            writer.write(&block);
+        }
+
+        let mut drained = Vec::new();
+        pop_available(&mut consumer, &mut drained);
+        assert_eq!(overflow_count.load(Ordering::Relaxed), 0);
+        assert_eq!(drained.len(), native_rate as usize * seconds);
+        assert!((drained[drained.len() - 1] - 0.2395).abs() < 1e-6);
+    }
+
+    #[test]
+    fn capture_writer_counts_dropped_samples_when_ring_is_full() {
+        let (producer, mut consumer) = HeapRb::<f32>::new(4).split();
+        let overflow_count = Arc::new(AtomicUsize::new(0));
+        let mixer = ChannelMixer::new(ChannelSelection::Average, 2).expect("stereo mixer");
+        let mut writer = CaptureWriter::new(producer, mixer, Arc::clone(&overflow_count));
+
+        writer.write(&[0.2f32, 0.4, 0.6, 0.8, -0.2, -0.4]);
+        writer.write(&[1i16 << 14, 1 << 14, 0, 0]);
+        assert_eq!(overflow_count.load(Ordering::Relaxed), 1);
+
+        let mut drained = vec![9.0];
+        pop_available(&mut consumer, &mut drained);
+        assert_eq!(drained.len(), 5);
+        assert!((drained[1] - 0.3).abs() < 1e-6);
+        assert!((drained[4] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn find_device_prefers_exact_names_then_ids_then_unique_substrings() {
+        let names = vec![
+            "default".to_string(),
+            "hw:CARD=USB,DEV=0".to_string(),
+            "Blue Yeti USB Microphone".to_string(),
+            "Built-in Audio Analog Stereo".to_string(),
+        ];
+
+        assert_eq!(find_device("alsa", &names, "DEFAULT").ok(), Some(0));
+        assert_eq!(
+            find_device("alsa", &names, "alsa:hw:CARD=USB,DEV=0").ok(),
+            Some(1)
+        );
+        assert_eq!(find_device("alsa", &names, "yeti").ok(), Some(2));
+        assert_eq!(find_device("alsa", &names, "builtin audio").ok(), Some(3));
+
+        let ambiguous = find_device("alsa", &names, "usb").expect_err("usb matches two devices");
+        assert!(ambiguous.message.contains("ambiguous"));
+        assert_eq!(
+            find_device("alsa", &names, "webcam")
+                .map_err(|err| err.kind)
+                .err(),
+            Some(AudioErrorKind::DeviceNotFound)
+        );
+    }
+
+    #[test]
+    fn find_device_ids_only_match_their_own_host() {
+        // Both hosts expose a device called "default".
+        let alsa = vec!["default".to_string(), "hw:CARD=USB,DEV=0".to_string()];
+        let jack = vec!["default".to_string(), "system".to_string()];
+
+        assert_eq!(find_device("jack", &jack, "jack:default").ok(), Some(0));
+        assert_eq!(
+            find_device("alsa", &alsa, "jack:default")
+                .map_err(|err| err.kind)
+                .err(),
+            Some(AudioErrorKind::DeviceNotFound)
+        );
+        assert_eq!(find_device("alsa", &alsa, "ALSA:default").ok(), Some(0));
+        assert!(find_device("jack", &jack, "alsa:hw:CARD=USB,DEV=0").is_err());
+    }
+
+    #[test]
+    fn channel_mixer_selects_index_average_or_loudest_channel() {
+        let interleaved = [0.1, 0.5, 0.1, 0.5, 0.1, 0.5];
+
+        let mut mono = Vec::new();
+        let mut average = ChannelMixer::new(ChannelSelection::Average, 2).expect("mixer");
+        average.mix(&interleaved, &mut mono);
+        assert!(mono.iter().all(|sample| (sample - 0.3).abs() < 1e-6));
+
+        mono.clear();
+        let mut first = ChannelMixer::new(ChannelSelection::Index(1), 2).expect("mixer");
+        first.mix(&interleaved, &mut mono);
+        assert_eq!(mono, vec![0.1, 0.1, 0.1]);
+
+        mono.clear();
+        let mut loudest = ChannelMixer::new(ChannelSelection::MaxEnergy, 2).expect("mixer");
+        loudest.mix(&interleaved, &mut mono);
+        assert_eq!(mono, vec![0.5, 0.5, 0.5]);
+
+        let err = match ChannelMixer::new(ChannelSelection::Index(3), 2) {
+            Ok(_) => panic!("expected missing channel error"),
+            Err(err) => err,
+        };
+        assert_eq!(err.kind, AudioErrorKind::StreamConfig);
+    }
+
+    fn test_vad(silence_timeout_ms: u64) -> VadConfig {
+        VadConfig::new(true, silence_timeout_ms, 0.015, 100, false)
+    }
+
+    fn energy_chunks(samples: &[f32], vad: &VadConfig) -> Vec<VadChunk> {
+        let mut detector = EnergyVad::new(vad.energy_threshold);
+        analyze_speech(samples, 16_000, vad, &mut detector).expect("energy analysis")
+    }
+
+    #[test]
+    fn trim_trailing_silence_stops_at_silence_timeout() {
+        let vad = test_vad(300);
+        let mut samples = vec![0.2; 1_600];
+        samples.extend(vec![0.0; 8_000]);
+        let chunks = energy_chunks(&samples, &vad);
+
+        let trimmed = trim_trailing_silence(&samples, 16_000, &vad, &chunks);
+
+        assert_eq!(trimmed.len(), 1_600 + 3_200);
+    }
+
+    #[test]
+    fn trim_leading_silence_keeps_one_chunk_of_lead_in() {
+        let vad = test_vad(300);
+        let mut samples = vec![0.0; 4_800];
+        samples.extend(vec![0.2; 1_600]);
+        let chunks = energy_chunks(&samples, &vad);
+
+        let trimmed = trim_leading_silence(&samples, &vad, &chunks);
+
+        assert_eq!(trimmed.len(), 3_200);
+        assert_eq!(trimmed[0], 0.0);
+        assert_eq!(trimmed[1_600], 0.2);
+    }
+
+    #[test]
+    fn trim_leading_silence_drops_buffers_without_speech() {
+        let vad = test_vad(300);
+        let samples = vec![0.001; 4_800];
+        let chunks = energy_chunks(&samples, &vad);
+
+        assert!(trim_leading_silence(&samples, &vad, &chunks).is_empty());
+        assert!(!contains_speech(&chunks));
+    }
+
+    #[test]
+    fn contains_speech_detects_chunk_above_threshold() {
+        let vad = test_vad(300);
+        let mut samples = vec![0.0; 4_800];
+        samples.extend(vec![0.2; 1_600]);
+
+        assert!(contains_speech(&energy_chunks(&samples, &vad)));
+    }
+
+    #[test]
+    fn calibrate_noise_floor_scales_median_chunk_level() {
+        let vad = test_vad(300);
+        let mut samples = vec![0.004; 4_800];
+        samples.extend(vec![0.3; 1_600]);
+
+        let calibration = calibrate_noise_floor(&samples, 16_000, &vad).expect("calibration");
+
+        assert!((calibration.noise_floor - 0.004).abs() < 1e-6);
+        assert!((calibration.threshold - 0.012).abs() < 1e-6);
+    }
+
+    #[test]
+    fn calibrate_noise_floor_clamps_threshold() {
+        let vad = test_vad(300);
+
+        let silent = calibrate_noise_floor(&[0.0; 1_600], 16_000, &vad).expect("calibration");
+        let loud = calibrate_noise_floor(&[0.5; 1_600], 16_000, &vad).expect("calibration");
+
+        assert_eq!(silent.threshold, MIN_VAD_THRESHOLD);
+        assert_eq!(loud.threshold, MAX_VAD_THRESHOLD);
+        assert!(calibrate_noise_floor(&[], 16_000, &vad).is_none());
+    }
+
+    #[test]
+    fn calibrate_from_silence_ignores_speech_chunks() {
+        let vad = test_vad(300);
+        // Mostly speech: the plain median would land on a speech chunk.
+        let mut samples = vec![0.004; 3_200];
+        samples.extend(vec![0.3; 4_800]);
+        let mut detector = EnergyVad::new(vad.energy_threshold);
+
+        let calibration = calibrate_from_silence(&samples, 16_000, &vad, &mut detector)
+            .expect("analysis")
+            .expect("calibration");
+
+        assert!((calibration.noise_floor - 0.004).abs() < 1e-6);
+        let median = calibrate_noise_floor(&samples, 16_000, &vad).expect("calibration");
+        assert!((median.noise_floor - 0.3).abs() < 1e-4);
+    }
+
+    #[test]
+    fn calibrate_from_silence_uses_quietest_chunk_above_threshold() {
+        let vad = test_vad(300);
+        let mut samples = vec![0.03; 3_200];
+        samples.extend(vec![0.3; 1_600]);
+        let mut detector = EnergyVad::new(vad.energy_threshold);
+
+        let calibration = calibrate_from_silence(&samples, 16_000, &vad, &mut detector)
+            .expect("analysis")
+            .expect("calibration");
+
+        assert!((calibration.noise_floor - 0.03).abs() < 1e-6);
+    }
+
+    #[test]
+    fn native_rate_prefers_target_then_closest_supported_rate() {
+        assert_eq!(native_rate_for(8_000, 48_000, 16_000), 16_000);
+        assert_eq!(native_rate_for(44_100, 48_000, 16_000), 44_100);
+        assert_eq!(native_rate_for(48_000, 48_000, 16_000), 48_000);
+        assert_eq!(native_rate_for(8_000, 11_025, 16_000), 11_025);
+    }
+
+    #[test]
+    fn pre_roll_buffer_keeps_most_recent_samples() {
+        let mut pre_roll = PreRollBuffer::new(4);
+        pre_roll.push(&[1.0, 2.0, 3.0]);
+        pre_roll.push(&[4.0, 5.0]);
+        assert_eq!(pre_roll.take(), vec![2.0, 3.0, 4.0, 5.0]);
+        assert!(pre_roll.take().is_empty());
+
+        pre_roll.push(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(pre_roll.take(), vec![3.0, 4.0, 5.0, 6.0]);
+
+        let mut disabled = PreRollBuffer::new(0);
+        disabled.push(&[1.0]);
+        assert!(disabled.take().is_empty());
+    }
+
+    #[test]
+    fn measure_level_reports_dbfs_and_clipping() {
+        let quiet = measure_level(&[0.1, -0.1, 0.1, -0.1]);
+        assert!((quiet.rms_dbfs + 20.0).abs() < 0.01);
+        assert!((quiet.peak_dbfs + 20.0).abs() < 0.01);
+        assert!(!quiet.clipping);
+
+        let clipped = measure_level(&[0.2, -1.0]);
+        assert_eq!(clipped.peak_dbfs, 0.0);
+        assert!(clipped.clipping);
+
+        assert_eq!(measure_level(&[0.0; 16]).rms_dbfs, MIN_DBFS);
+    }
+
+    #[test]
+    fn frame_probabilities_map_to_chunk_scores() {
+        let probabilities = [0.1, 0.2, 0.9, 0.7, 0.1];
+
+        let chunks = chunks_from_frame_probabilities(2_400, 1_000, 512, &probabilities, 0.5);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].frame_probabilities, vec![0.1, 0.2]);
+        assert!(!chunks[0].speech);
+        assert_eq!(chunks[1].frame_probabilities, vec![0.2, 0.9, 0.7]);
+        assert_eq!(chunks[1].score, 0.9);
+        assert!(chunks[1].speech);
+        assert_eq!(chunks[2].len, 400);
+        assert_eq!(chunks[2].frame_probabilities, vec![0.7, 0.1]);
+    }
+
+    #[test]
+    fn silence_tracker_waits_for_speech_before_counting_silence() {
+        let vad = test_vad(300);
+        let mut detector = EnergyVad::new(vad.energy_threshold);
+        let mut tracker = SilenceTracker::new(16_000, &vad);
+        let samples = vec![0.0; 16_000];
+
+        assert!(!tracker.update(&samples, &mut detector).expect("update"));
+        assert!(!tracker.speech_detected());
+    }
+
+    #[test]
+    fn silence_tracker_stops_after_trailing_silence_timeout() {
+        let vad = test_vad(300);
+        let mut detector = EnergyVad::new(vad.energy_threshold);
+        let mut tracker = SilenceTracker::new(16_000, &vad);
+        let mut samples = vec![0.2; 1_600];
+        assert!(!tracker.update(&samples, &mut detector).expect("update"));
+        assert!(tracker.speech_detected());
+
+        samples.extend(vec![0.0; 3_200]);
+        assert!(!tracker.update(&samples, &mut detector).expect("update"));
+
+        samples.extend(vec![0.0; 1_600]);
+        assert!(tracker.update(&samples, &mut detector).expect("update"));
+    }
+
+    #[test]
+    fn silence_tracker_resets_silence_when_speech_resumes() {
+        let vad = test_vad(300);
+        let mut detector = EnergyVad::new(vad.energy_threshold);
+        let mut tracker = SilenceTracker::new(16_000, &vad);
+        let mut samples = vec![0.2; 1_600];
+        samples.extend(vec![0.0; 3_200]);
+        samples.extend(vec![0.2; 1_600]);
+        samples.extend(vec![0.0; 3_200]);
+
+        assert!(!tracker.update(&samples, &mut detector).expect("update"));
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::audio::NoiseCalibration;
+use crate::error::AppError;
+
+const DEFAULT_DEVICE_KEY: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceCalibration {
+    pub noise_floor: f32,
+    pub vad_threshold: f32,
+    pub calibrated_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CalibrationStore {
+    #[serde(default)]
+    devices: BTreeMap<String, DeviceCalibration>,
+}
+
+impl CalibrationStore {
+    pub fn load(path: &Path) -> Result<Self, AppError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).map_err(|err| {
+            AppError::config(format!(
+                "failed to read calibration file {}: {err}",
+                path.display()
+            ))
+        })?;
+        toml::from_str(&contents).map_err(|err| {
+            AppError::config(format!(
+                "failed to parse calibration file {}: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                AppError::config(format!(
+                    "failed to create calibration directory {}: {err}",
+                    parent.display()
+                ))
+            })?;
+        }
+        let contents = toml::to_string_pretty(self)
+            .map_err(|err| AppError::config(format!("failed to encode calibration: {err}")))?;
+        fs::write(path, contents).map_err(|err| {
+            AppError::config(format!(
+                "failed to write calibration file {}: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    pub fn get(&self, device: Option<&str>) -> Option<&DeviceCalibration> {
+        self.devices.get(&device_key(device))
+    }
+
+    pub fn thresholds(&self) -> HashMap<String, f32> {
+        self.devices
+            .iter()
+            .map(|(device, entry)| (device.clone(), entry.vad_threshold))
+            .collect()
+    }
+
+    pub fn insert(
+        &mut self,
+        device: Option<&str>,
+        calibration: &NoiseCalibration,
+        calibrated_at: String,
+    ) {
+        self.devices.insert(
+            device_key(device),
+            DeviceCalibration {
+                noise_floor: calibration.noise_floor,
+                vad_threshold: calibration.threshold,
+                calibrated_at,
+            },
+        );
+    }
+}
+
+pub fn device_key(device: Option<&str>) -> String {
+    device
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_DEVICE_KEY.to_string())
+}
+
+pub fn calibration_path() -> PathBuf {
+    let state_home = env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .unwrap_or_else(|| PathBuf::from("/tmp"));
+    state_home.join("soundvibes").join("calibration.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_round_trips_per_device() {
+        let path = env::temp_dir()
+            .join(format!("soundvibes-calibration-{}", std::process::id()))
+            .join("calibration.toml");
+        let mut store = CalibrationStore::default();
+        store.insert(
+            Some("USB Mic"),
+            &NoiseCalibration {
+                noise_floor: 0.004,
+                threshold: 0.012,
+            },
+            "2026-02-05T12:00:00Z".to_string(),
+        );
+        store.save(&path).expect("save calibration");
+
+        let loaded = CalibrationStore::load(&path).expect("load calibration");
+        let _ = fs::remove_dir_all(path.parent().expect("calibration dir"));
+
+        let entry = loaded.get(Some("usb mic")).expect("device calibration");
+        assert_eq!(entry.vad_threshold, 0.012);
+        assert!(loaded.get(None).is_none());
+    }
+}
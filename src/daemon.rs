@@ -12,31 +12,68 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::audio;
+use crate::calibration;
 use crate::error::AppError;
+use crate::file_capture;
+use crate::hardware;
 use crate::ipc;
-use crate::model::{self, ModelLanguage, ModelSize, ModelSpec, ModelVariants};
+use crate::model::{
+    self, ModelId, ModelLanguage, ModelRef, ModelSize, ModelSource, ModelSpec, ModelVariants,
+};
 use crate::output;
-use crate::types::{AudioHost, OutputFormat, OutputMode, VadMode};
-use crate::whisper::WhisperContext;
+use crate::preprocess;
+use crate::sound_server;
+use crate::types::{
+    AudioHost, ChannelSelection, DeviceFallback, GpuSelection, NormalizeMode, OutputFormat,
+    OutputMode, ResampleQuality, VadBackend, VadMode,
+};
+use crate::whisper::{self, WhisperContext, WhisperVadContext};
 
 #[derive(Debug, Clone)]
 pub struct DaemonConfig {
-    pub model_size: ModelSize,
+    pub model_size: ModelRef,
+    pub models: HashMap<String, PathBuf>,
+    pub languages: HashMap<String, ModelRef>,
+    pub model_idle_unload_ms: u64,
+    pub model_memory_budget_mb: u64,
+    pub model_auto_benchmark: bool,
+    pub model_auto_target_rtf: f32,
+    pub gpu: GpuSelection,
+    pub flash_attn: bool,
     pub model_variants: ModelVariants,
     pub download_model: bool,
+    pub verify_model_checksum: bool,
     pub language: String,
     pub device: Option<String>,
     pub audio_host: AudioHost,
+    pub device_fallback: DeviceFallback,
+    pub channel: ChannelSelection,
     pub sample_rate: u32,
     pub format: OutputFormat,
     pub mode: OutputMode,
     pub vad: VadMode,
     pub vad_silence_ms: u64,
     pub vad_threshold: f32,
+    // Thresholds saved by `sv calibrate`, keyed by `calibration::device_key`.
+    pub device_thresholds: HashMap<String, f32>,
     pub vad_chunk_ms: u64,
+    pub vad_auto_stop: bool,
+    pub vad_backend: VadBackend,
+    pub vad_model: Option<PathBuf>,
+    pub vad_probability: f32,
+    pub vad_auto_calibrate: bool,
+    pub vad_calibration_ms: u64,
+    pub audio_level_interval_ms: u64,
+    pub warm_capture: bool,
+    pub pre_roll_ms: u64,
+    pub resample_quality: ResampleQuality,
+    pub input_gain_db: f32,
+    pub normalize: NormalizeMode,
+    pub dc_filter: bool,
+    pub denoise: bool,
     pub debug_audio: bool,
     pub debug_vad: bool,
     pub dump_audio: bool,
@@ -61,10 +98,50 @@ impl DaemonOutput for StdoutOutput {
 
 pub trait CaptureSource {
     fn drain(&mut self, output: &mut Vec<f32>);
+
+    // Returns a reason once the underlying device has gone away; the daemon
+    // then drops the capture and reopens it on the next toggle.
+    fn device_lost(&mut self) -> Option<String> {
+        None
+    }
+
+    // True once a finite source such as a file has delivered all of its audio;
+    // the daemon then ends the recording.
+    fn end_of_input(&mut self) -> bool {
+        false
+    }
+
+    // Samples dropped because the capture could not keep up since the last call.
+    fn take_overflow(&mut self) -> usize {
+        0
+    }
+
+    // Lines describing the opened stream, printed once when capture starts.
+    fn details(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    // Name of the device actually opened, which may differ from the one requested.
+    fn device_id(&self) -> Option<String> {
+        None
+    }
 }
 
 pub trait AudioBackend {
     fn list_input_devices(&self, host: &cpal::Host) -> Result<Vec<String>, audio::AudioError>;
+    fn describe_input_devices(
+        &self,
+        host: &cpal::Host,
+    ) -> Result<Vec<audio::InputDeviceInfo>, audio::AudioError> {
+        Ok(self
+            .list_input_devices(host)?
+            .into_iter()
+            .map(audio::InputDeviceInfo::named)
+            .collect())
+    }
+    fn device_exists(&self, host: &cpal::Host, devices: &[String], requested: &str) -> bool {
+        audio::find_device(&audio::host_name(host), devices, requested).is_ok()
+    }
     fn start_capture(
         &self,
         host: &cpal::Host,
@@ -75,14 +152,30 @@ pub trait AudioBackend {
 
 pub trait Transcriber {
     fn transcribe(&self, samples: &[f32], language: Option<&str>) -> Result<String, AppError>;
+
+    // Approximate resident size, counted against `model_memory_budget_mb`.
+    fn memory_bytes(&self) -> u64 {
+        0
+    }
+
+    // The inference backend in use (e.g. `Vulkan0` or `cpu`), when known.
+    fn backend(&self) -> Option<&str> {
+        None
+    }
 }
 
 pub trait TranscriberFactory {
     fn load(
         &self,
-        spec: &ModelSpec,
+        source: &ModelSource,
         allow_download: bool,
+        progress: &mut dyn FnMut(model::DownloadProgress),
     ) -> Result<Box<dyn Transcriber>, AppError>;
+
+    // The GPU backend loaded models would run on, known without loading one.
+    fn gpu_backend(&self) -> Option<String> {
+        None
+    }
 }
 
 pub struct DaemonDeps {
@@ -93,20 +186,63 @@ pub struct DaemonDeps {
 impl Default for DaemonDeps {
     fn default() -> Self {
         Self {
-            audio: Box::new(CpalAudioBackend),
-            transcriber_factory: Box::new(WhisperFactory),
+            audio: Box::new(CpalAudioBackend {
+                resample_quality: ResampleQuality::Sinc,
+                channel: ChannelSelection::Average,
+            }),
+            transcriber_factory: Box::new(WhisperFactory {
+                verify_checksum: false,
+                options: whisper::ContextOptions::default(),
+            }),
+        }
+    }
+}
+
+impl DaemonDeps {
+    pub fn for_config(config: &DaemonConfig) -> Self {
+        let audio: Box<dyn AudioBackend> = match config.audio_host {
+            AudioHost::Pipewire => Box::new(sound_server::PipeWireBackend {
+                resample_quality: config.resample_quality,
+                channel: config.channel,
+            }),
+            AudioHost::Pulse => Box::new(sound_server::PulseBackend {
+                resample_quality: config.resample_quality,
+                channel: config.channel,
+            }),
+            AudioHost::File => Box::new(file_capture::FileBackend::new(
+                config.resample_quality,
+                config.channel,
+            )),
+            AudioHost::Default | AudioHost::Alsa => Box::new(CpalAudioBackend {
+                resample_quality: config.resample_quality,
+                channel: config.channel,
+            }),
+        };
+        Self {
+            audio,
+            transcriber_factory: Box::new(WhisperFactory {
+                verify_checksum: config.verify_model_checksum,
+                options: whisper::ContextOptions {
+                    use_gpu: config.gpu != GpuSelection::Off,
+                    gpu_device: match config.gpu {
+                        GpuSelection::Device(index) => index as i32,
+                        GpuSelection::Auto | GpuSelection::Off => 0,
+                    },
+                    flash_attn: config.flash_attn,
+                },
+            }),
         }
     }
 }
 
 pub fn select_audio_host(audio_host: AudioHost) -> Result<cpal::Host, AppError> {
     match audio_host {
-        AudioHost::Default => Ok(cpal::default_host()),
+        // Sound-server and file backends capture on their own; the cpal host is unused.
+        AudioHost::Default | AudioHost::Pipewire | AudioHost::Pulse | AudioHost::File => {
+            Ok(cpal::default_host())
+        }
         _ => {
-            let host_id = match audio_host {
-                AudioHost::Alsa => cpal::HostId::Alsa,
-                AudioHost::Default => cpal::HostId::Alsa,
-            };
+            let host_id = cpal::HostId::Alsa;
             if !cpal::available_hosts().contains(&host_id) {
                 let available = cpal::available_hosts()
                     .into_iter()
@@ -127,6 +263,8 @@ pub fn select_audio_host(audio_host: AudioHost) -> Result<cpal::Host, AppError>
 pub enum ControlEvent {
     Toggle { language: Option<String> },
     Status,
+    Devices,
+    SetDevice { device: String },
     SetLanguage { language: String },
     Stop,
     Error(String),
@@ -141,20 +279,46 @@ struct ModelPoolEntry {
     transcriber: Box<dyn Transcriber>,
     model_size: String,
     model_language: String,
+    last_used: Instant,
 }
 
+// Loaded models keyed by file, so languages mapped to the same model share one
+// context. Models for `model_variants` load at startup; models only used by a
+// `[languages.<code>]` mapping load on first use and are dropped once another
+// model becomes active. Idle models and least recently used models over the
+// memory budget are unloaded and reload on next use.
 struct ModelPool {
-    entries: HashMap<ModelLanguage, ModelPoolEntry>,
+    entries: HashMap<ModelSource, ModelPoolEntry>,
+    pinned: Vec<ModelSource>,
+    idle_unload: Option<Duration>,
+    memory_budget: Option<u64>,
+    event_sender: Option<mpsc::Sender<ipc::DaemonEvent>>,
+    // Warnings from loads, reported by the daemon loop through its output.
+    warnings: Vec<String>,
 }
 
 impl ModelPool {
-    fn preload(config: &DaemonConfig, deps: &DaemonDeps) -> Result<Self, AppError> {
+    fn preload(
+        config: &DaemonConfig,
+        deps: &DaemonDeps,
+        event_sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
+    ) -> Result<Self, AppError> {
+        model::validate_aliases(&config.models)?;
         let mut pool = Self {
             entries: HashMap::new(),
+            pinned: Vec::new(),
+            idle_unload: (config.model_idle_unload_ms > 0)
+                .then(|| Duration::from_millis(config.model_idle_unload_ms)),
+            memory_budget: (config.model_memory_budget_mb > 0)
+                .then(|| config.model_memory_budget_mb * 1024 * 1024),
+            event_sender: event_sender.cloned(),
+            warnings: Vec::new(),
         };
 
         for &model_language in config.model_variants.preload() {
-            pool.load_variant(model_language, config, deps)?;
+            let source = config.model_size.source(model_language, &config.models)?;
+            pool.load_source(&source, model_language, config, deps)?;
+            pool.pinned.push(source);
         }
 
         let active_language = normalize_language(&config.language);
@@ -168,68 +332,191 @@ impl ModelPool {
         language: &str,
         config: &DaemonConfig,
         deps: &DaemonDeps,
-    ) -> Result<ModelLanguage, AppError> {
+    ) -> Result<ModelSource, AppError> {
         let preferred = model::model_language_for_transcription(language);
-        let selected = self
-            .select_variant(preferred, config.model_variants)
-            .ok_or_else(|| {
-                AppError::config(format!(
-                    "no compatible model variant configured for language '{language}'"
-                ))
-            })?;
+        let (source, model_language) = match config.languages.get(language) {
+            Some(model) => (model.source(preferred, &config.models)?, preferred),
+            None => {
+                let selected = self
+                    .select_variant(preferred, config.model_variants)
+                    .ok_or_else(|| {
+                        AppError::config(format!(
+                            "no compatible model variant configured for language '{language}'"
+                        ))
+                    })?;
+                (
+                    config.model_size.source(selected, &config.models)?,
+                    selected,
+                )
+            }
+        };
 
-        if !self.entries.contains_key(&selected) {
-            self.load_variant(selected, config, deps)?;
+        self.load_source(&source, model_language, config, deps)?;
+        if let Some(entry) = self.entries.get_mut(&source) {
+            entry.last_used = Instant::now();
+        }
+        let pinned = &self.pinned;
+        let unused = self
+            .entries
+            .keys()
+            .filter(|loaded| **loaded != source && !pinned.contains(loaded))
+            .cloned()
+            .collect::<Vec<_>>();
+        for loaded in unused {
+            self.unload(&loaded, "language_switch");
         }
-        Ok(selected)
+        Ok(source)
     }
 
-    fn set_entry(
+    // Loads `source` again if it was unloaded and marks it as used. Returns
+    // whether it had to be loaded.
+    fn ensure_loaded(
         &mut self,
-        variant: ModelLanguage,
-        transcriber: Box<dyn Transcriber>,
-        model_size: String,
-        model_language: String,
-    ) {
-        self.entries.insert(
-            variant,
-            ModelPoolEntry {
-                transcriber,
-                model_size,
-                model_language,
-            },
-        );
+        source: &ModelSource,
+        language: &str,
+        config: &DaemonConfig,
+        deps: &DaemonDeps,
+    ) -> Result<bool, AppError> {
+        let loaded = self.load_source(
+            source,
+            model::model_language_for_transcription(language),
+            config,
+            deps,
+        )?;
+        if let Some(entry) = self.entries.get_mut(source) {
+            entry.last_used = Instant::now();
+        }
+        Ok(loaded)
     }
 
-    fn transcriber_for_variant(&self, variant: ModelLanguage) -> Option<&dyn Transcriber> {
+    fn transcriber_for(&self, source: &ModelSource) -> Option<&dyn Transcriber> {
         self.entries
-            .get(&variant)
+            .get(source)
             .map(|entry| entry.transcriber.as_ref())
     }
 
-    fn metadata_for_variant(&self, variant: ModelLanguage) -> Option<(&str, &str)> {
+    fn metadata_for(&self, source: &ModelSource) -> Option<(&str, &str)> {
         self.entries
-            .get(&variant)
+            .get(source)
             .map(|entry| (entry.model_size.as_str(), entry.model_language.as_str()))
     }
 
-    fn load_variant(
+    fn backend_for(&self, source: &ModelSource) -> Option<&str> {
+        self.entries
+            .get(source)
+            .and_then(|entry| entry.transcriber.backend())
+    }
+
+    fn load_source(
         &mut self,
+        source: &ModelSource,
         variant: ModelLanguage,
         config: &DaemonConfig,
         deps: &DaemonDeps,
-    ) -> Result<(), AppError> {
-        let spec = ModelSpec::new(config.model_size, variant);
-        let transcriber = deps
-            .transcriber_factory
-            .load(&spec, config.download_model)?;
-        self.set_entry(
-            variant,
-            transcriber,
-            model_size_token(spec.size).to_string(),
-            model_language_token(spec.language).to_string(),
+    ) -> Result<bool, AppError> {
+        if self.entries.contains_key(source) {
+            return Ok(false);
+        }
+        let filename = source
+            .path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let event_sender = self.event_sender.as_ref();
+        let transcriber =
+            deps.transcriber_factory
+                .load(source, config.download_model, &mut |progress| {
+                    emit_daemon_event(
+                        event_sender,
+                        ipc::DaemonEventType::ModelDownloadProgress {
+                            model: filename.clone(),
+                            downloaded_bytes: progress.downloaded_bytes,
+                            total_bytes: progress.total_bytes,
+                        },
+                    )
+                })?;
+        let model_language = match source {
+            ModelSource::Published(spec) => spec.language,
+            ModelSource::Custom { .. } => variant,
+        };
+        if config.verify_model_checksum
+            && matches!(source, ModelSource::Published(_))
+            && model::expected_checksum(&filename).is_none()
+        {
+            self.warnings.push(format!(
+                "warning: no checksum known for {filename}; skipping verification"
+            ));
+        }
+        self.entries.insert(
+            source.clone(),
+            ModelPoolEntry {
+                transcriber,
+                model_size: source.name(),
+                model_language: model_language_token(model_language).to_string(),
+                last_used: Instant::now(),
+            },
+        );
+        self.enforce_memory_budget(source);
+        Ok(true)
+    }
+
+    // Unloads least recently used models until the pool fits the budget;
+    // `keep` (the model just loaded or activated) always stays.
+    fn enforce_memory_budget(&mut self, keep: &ModelSource) {
+        let Some(budget) = self.memory_budget else {
+            return;
+        };
+        loop {
+            let total = self
+                .entries
+                .values()
+                .map(|entry| entry.transcriber.memory_bytes())
+                .sum::<u64>();
+            if total <= budget {
+                return;
+            }
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .filter(|(source, _)| *source != keep)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(source, _)| source.clone())
+            else {
+                return;
+            };
+            self.unload(&oldest, "memory_budget");
+        }
+    }
+
+    // The active model is never idle: it is the one the next toggle needs.
+    fn unload_idle(&mut self, now: Instant, active: &ModelSource) {
+        let Some(timeout) = self.idle_unload else {
+            return;
+        };
+        let idle = self
+            .entries
+            .iter()
+            .filter(|(source, _)| *source != active)
+            .filter(|(_, entry)| now.saturating_duration_since(entry.last_used) >= timeout)
+            .map(|(source, _)| source.clone())
+            .collect::<Vec<_>>();
+        for source in idle {
+            self.unload(&source, "idle");
+        }
+    }
+
+    fn unload(&mut self, source: &ModelSource, reason: &str) {
+        let Some(entry) = self.entries.remove(source) else {
+            return;
+        };
+        emit_daemon_event(
+            self.event_sender.as_ref(),
+            ipc::DaemonEventType::ModelUnloaded {
+                model_size: entry.model_size,
+                model_language: entry.model_language,
+                reason: reason.to_string(),
+            },
         );
-        Ok(())
     }
 
     fn select_variant(
@@ -282,6 +569,92 @@ pub fn run_daemon(
     )
 }
 
+const AUTO_BENCHMARK_SECONDS: f32 = 5.0;
+const WHISPER_SAMPLE_RATE: usize = 16_000;
+
+// Replaces `auto` model sizes with the largest size this machine can run at
+// `model_auto_target_rtf`. Returns `None` when no model uses `auto`.
+fn resolve_auto_model(
+    config: &DaemonConfig,
+    deps: &DaemonDeps,
+    output: &mut dyn DaemonOutput,
+) -> Result<Option<DaemonConfig>, AppError> {
+    let is_auto =
+        |model: &ModelRef| matches!(model, ModelRef::Builtin(id) if id.size == ModelSize::Auto);
+    if !is_auto(&config.model_size) && !config.languages.values().any(is_auto) {
+        return Ok(None);
+    }
+
+    let gpu_backend = match config.gpu {
+        GpuSelection::Off => None,
+        _ => deps.transcriber_factory.gpu_backend(),
+    };
+    // Only the benchmark needs a model; the backend comes from the factory.
+    let tiny_rtf = if config.model_auto_benchmark {
+        match benchmark_tiny_model(config, deps) {
+            Ok(rtf) => Some(rtf),
+            Err(err) => {
+                output.stderr(&format!("warning: auto model benchmark failed: {err}"));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let profile = hardware::HardwareProfile::probe(gpu_backend);
+    let choice = hardware::choose_model_size(
+        &profile,
+        tiny_rtf,
+        config.model_auto_target_rtf,
+        config.model_variants.preload(),
+    );
+    output.stdout(&format!(
+        "Auto model size: {} ({profile}; {} real-time factor {:.2}, target {:.2})",
+        choice.size,
+        if choice.measured {
+            "measured"
+        } else {
+            "estimated"
+        },
+        choice.estimated_rtf,
+        config.model_auto_target_rtf
+    ));
+
+    let resolve = |model: &ModelRef| match model {
+        ModelRef::Builtin(id) if id.size == ModelSize::Auto => ModelRef::Builtin(ModelId {
+            size: choice.size,
+            quantization: id.quantization,
+        }),
+        other => other.clone(),
+    };
+    let mut resolved = config.clone();
+    resolved.model_size = resolve(&config.model_size);
+    resolved.languages = config
+        .languages
+        .iter()
+        .map(|(language, model)| (language.clone(), resolve(model)))
+        .collect();
+    Ok(Some(resolved))
+}
+
+// Times tiny on a few seconds of low-level noise.
+fn benchmark_tiny_model(config: &DaemonConfig, deps: &DaemonDeps) -> Result<f32, AppError> {
+    let source = ModelSource::Published(ModelSpec::new(ModelSize::Tiny, ModelLanguage::Auto));
+    let transcriber = deps
+        .transcriber_factory
+        .load(&source, config.download_model, &mut |_| {})?;
+    let mut seed = 0x2545_f491u32;
+    let samples = (0..(AUTO_BENCHMARK_SECONDS * WHISPER_SAMPLE_RATE as f32) as usize)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            ((seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5) * 0.01
+        })
+        .collect::<Vec<_>>();
+    let started = Instant::now();
+    transcriber.transcribe(&samples, Some("en"))?;
+    Ok(started.elapsed().as_secs_f32() / AUTO_BENCHMARK_SECONDS)
+}
+
 pub fn run_daemon_loop(
     config: &DaemonConfig,
     deps: &DaemonDeps,
@@ -290,7 +663,9 @@ pub fn run_daemon_loop(
     shutdown: &AtomicBool,
     event_sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
 ) -> Result<(), AppError> {
-    let mut model_pool = ModelPool::preload(config, deps)?;
+    let resolved_config = resolve_auto_model(config, deps, output)?;
+    let config = resolved_config.as_ref().unwrap_or(config);
+    let mut model_pool = ModelPool::preload(config, deps, event_sender)?;
     let host = select_audio_host(config.audio_host)?;
     audio::configure_alsa_logging(config.debug_audio);
     let devices = deps
@@ -302,9 +677,20 @@ pub fn run_daemon_loop(
         output.stdout(&format!("  - {name}"));
     }
 
+    let mut input_device = InputDevice {
+        selected: config.device.clone(),
+        ..InputDevice::default()
+    };
     if let Some(device) = config.device.as_deref() {
-        if !devices.iter().any(|name| name.eq_ignore_ascii_case(device)) {
-            return Err(AppError::audio(format!("input device not found: {device}")));
+        if !deps.audio.device_exists(&host, &devices, device) {
+            let message = format!("input device not found: {device}");
+            if config.device_fallback == DeviceFallback::Off {
+                return Err(AppError::audio(message));
+            }
+            output.stderr(&format!(
+                "{message}; using the default input device until it returns."
+            ));
+            input_device.mark_lost(device, message, event_sender);
         }
     }
 
@@ -315,41 +701,81 @@ pub fn run_daemon_loop(
         config.vad_chunk_ms,
         config.debug_vad,
     );
-
-    let mut recording = false;
-    let mut active_language = normalize_language(&config.language);
-    let mut active_variant = model_pool.resolve_language(active_language.as_str(), config, deps)?;
-    let mut buffer = Vec::new();
-    let mut utterance_index = 0u64;
-    let mut capture: Option<Box<dyn CaptureSource>> = None;
-
+    let detector = create_voice_detector(config, &vad)?;
+    let auto_stop = vad.enabled && config.vad_auto_stop;
+    let mut silence_tracker = audio::SilenceTracker::new(config.sample_rate, &vad);
+    let calibration_samples = if vad.enabled && config.vad_auto_calibrate {
+        audio::ms_to_samples(config.vad_calibration_ms, config.sample_rate)
+    } else {
+        0
+    };
+    let mut calibration_pending = false;
+    let level_samples = if event_sender.is_some() {
+        audio::ms_to_samples(config.audio_level_interval_ms, config.sample_rate)
+    } else {
+        0
+    };
+    let mut level_position = 0usize;
+
+    let active_language = normalize_language(&config.language);
+    let active_model = model_pool.resolve_language(active_language.as_str(), config, deps)?;
+    let mut state = DaemonState {
+        model_pool,
+        detector,
+        recording: false,
+        active_language,
+        active_model,
+        capture: None,
+        vad_threshold: config.vad_threshold,
+        conditioner: preprocess::InputConditioner::new(
+            config.sample_rate,
+            config.input_gain_db,
+            config.dc_filter,
+            config.denoise,
+        ),
+        raw: Vec::new(),
+        buffer: Vec::new(),
+        utterance_index: 0,
+    };
+    let mut reopen_capture = false;
+    let mut pre_roll =
+        audio::PreRollBuffer::new(audio::ms_to_samples(config.pre_roll_ms, config.sample_rate));
+    let mut idle_samples = Vec::new();
+    if config.warm_capture {
+        state.capture = Some(
+            open_capture(config, deps, &host, &mut input_device, output, event_sender)
+                .map_err(|err| AppError::audio(err.message))?,
+        );
+        state.conditioner.reset();
+        apply_device_threshold(&mut state, config, &input_device, output);
+        output.stdout(&format!(
+            "Capture kept warm with {} ms pre-roll.",
+            config.pre_roll_ms
+        ));
+    }
     emit_daemon_event(event_sender, ipc::DaemonEventType::DaemonReady);
-    emit_model_loaded_event(event_sender, &model_pool, &active_language, active_variant);
+    emit_model_loaded_event(
+        event_sender,
+        &state.model_pool,
+        &state.active_language,
+        &state.active_model,
+    );
 
     loop {
+        for warning in state.model_pool.warnings.drain(..) {
+            output.stderr(&warning);
+        }
         if shutdown.load(Ordering::Relaxed) {
-            if recording {
-                let active_transcriber = active_transcriber(&model_pool, active_variant)?;
-                let mut recording_context = RecordingContext {
-                    transcriber: active_transcriber,
+            if state.recording {
+                stop_recording(
+                    &mut state,
+                    ipc::StopReason::Shutdown,
                     config,
-                    language: active_language.as_str(),
-                    vad: &vad,
+                    deps,
+                    &vad,
                     output,
                     event_sender,
-                };
-                stop_recording(
-                    &mut recording_context,
-                    &mut capture,
-                    &mut buffer,
-                    &mut utterance_index,
                 )?;
-                emit_daemon_event(
-                    event_sender,
-                    ipc::DaemonEventType::RecordingStopped {
-                        language: active_language.clone(),
-                    },
-                );
             }
             output.stdout("Daemon shutting down.");
             break;
@@ -360,91 +786,188 @@ pub fn run_daemon_loop(
                     ControlEvent::Toggle { language } => {
                         if let Some(language) = language {
                             let normalized = normalize_language(&language);
-                            active_variant =
-                                model_pool.resolve_language(normalized.as_str(), config, deps)?;
-                            active_language = normalized;
+                            state.active_model = state.model_pool.resolve_language(
+                                normalized.as_str(),
+                                config,
+                                deps,
+                            )?;
+                            state.active_language = normalized;
                             emit_model_loaded_event(
                                 event_sender,
-                                &model_pool,
-                                &active_language,
-                                active_variant,
+                                &state.model_pool,
+                                &state.active_language,
+                                &state.active_model,
                             );
                         }
 
-                        if recording {
-                            recording = false;
-                            let active_transcriber =
-                                active_transcriber(&model_pool, active_variant)?;
-                            let mut recording_context = RecordingContext {
-                                transcriber: active_transcriber,
+                        if state.recording {
+                            stop_recording(
+                                &mut state,
+                                ipc::StopReason::Toggle,
                                 config,
-                                language: active_language.as_str(),
-                                vad: &vad,
+                                deps,
+                                &vad,
                                 output,
                                 event_sender,
-                            };
-                            stop_recording(
-                                &mut recording_context,
-                                &mut capture,
-                                &mut buffer,
-                                &mut utterance_index,
                             )?;
-                            emit_daemon_event(
-                                event_sender,
-                                ipc::DaemonEventType::RecordingStopped {
-                                    language: active_language.clone(),
-                                },
-                            );
-                            control_ok_response("idle", active_language.as_str())
+                            control_ok_response("idle", state.active_language.as_str())
                         } else {
-                            let new_capture = deps
-                                .audio
-                                .start_capture(&host, config.device.as_deref(), config.sample_rate)
-                                .map_err(|err| match err.kind {
-                                    audio::AudioErrorKind::DeviceNotFound
-                                        if config.device.is_some() =>
-                                    {
-                                        AppError::audio(err.message)
+                            // Reload the active model if the memory budget evicted it before
+                            // recording starts, so a slow load or download cannot overrun the
+                            // capture ring and lose the start of the utterance.
+                            if state.model_pool.ensure_loaded(
+                                &state.active_model,
+                                &state.active_language,
+                                config,
+                                deps,
+                            )? {
+                                emit_model_loaded_event(
+                                    event_sender,
+                                    &state.model_pool,
+                                    &state.active_language,
+                                    &state.active_model,
+                                );
+                            }
+                            if reopen_capture {
+                                reopen_capture = false;
+                                state.capture = None;
+                                pre_roll.take();
+                            }
+                            if state.capture.is_none() {
+                                match open_capture(
+                                    config,
+                                    deps,
+                                    &host,
+                                    &mut input_device,
+                                    output,
+                                    event_sender,
+                                ) {
+                                    Ok(new_capture) => {
+                                        state.capture = Some(new_capture);
+                                        state.conditioner.reset();
+                                        apply_device_threshold(
+                                            &mut state,
+                                            config,
+                                            &input_device,
+                                            output,
+                                        );
                                     }
-                                    _ => AppError::audio(err.message),
-                                })?;
-                            recording = true;
-                            buffer.clear();
-                            capture = Some(new_capture);
+                                    Err(err) => {
+                                        // Stay idle so the next toggle retries the device.
+                                        output.stderr(&format!("error: {}", err.message));
+                                        emit_daemon_event(
+                                            event_sender,
+                                            ipc::DaemonEventType::Error {
+                                                message: err.message.clone(),
+                                            },
+                                        );
+                                        if let Some(sender) = message.response {
+                                            let _ = sender.send(control_error_response(
+                                                "audio_error",
+                                                err.message,
+                                            ));
+                                        }
+                                        continue;
+                                    }
+                                }
+                            }
+                            state.recording = true;
+                            state.buffer.clear();
+                            state.buffer.extend(pre_roll.take());
+                            state.conditioner.take_clipping();
+                            silence_tracker.reset();
+                            calibration_pending = calibration_samples > 0;
+                            level_position = 0;
                             output.stdout("Toggle on. Recording...");
                             emit_daemon_event(
                                 event_sender,
                                 ipc::DaemonEventType::RecordingStarted {
-                                    language: active_language.clone(),
+                                    language: state.active_language.clone(),
                                 },
                             );
-                            control_ok_response("recording", active_language.as_str())
+                            control_ok_response("recording", state.active_language.as_str())
                         }
                     }
                     ControlEvent::Status => {
-                        let state = if recording { "recording" } else { "idle" };
-                        control_ok_response(state, active_language.as_str())
+                        let response =
+                            control_ok_response(state.status(), state.active_language.as_str())
+                                .with_device(input_device.selected_label());
+                        match state.model_pool.backend_for(&state.active_model) {
+                            Some(backend) => response.with_backend(backend),
+                            None => response,
+                        }
                     }
+                    ControlEvent::SetDevice { device } => {
+                        let requested = device.trim();
+                        let validated = if requested.eq_ignore_ascii_case(DEFAULT_DEVICE_LABEL) {
+                            Ok(None)
+                        } else {
+                            deps.audio.list_input_devices(&host).and_then(|devices| {
+                                if deps.audio.device_exists(&host, &devices, requested) {
+                                    Ok(Some(requested.to_string()))
+                                } else {
+                                    Err(audio::AudioError::new(
+                                        audio::AudioErrorKind::DeviceNotFound,
+                                        format!("input device not found: {requested}"),
+                                    ))
+                                }
+                            })
+                        };
+                        match validated {
+                            Ok(selected) => {
+                                input_device.select(selected);
+                                let device = input_device.selected_label();
+                                output.stdout(&format!("Input device set to {device}."));
+                                emit_daemon_event(
+                                    event_sender,
+                                    ipc::DaemonEventType::DeviceChanged {
+                                        device: device.clone(),
+                                    },
+                                );
+                                // The current recording keeps its stream; the next one reopens.
+                                if state.recording {
+                                    reopen_capture = true;
+                                } else {
+                                    state.capture = None;
+                                    pre_roll.take();
+                                }
+                                control_ok_response(state.status(), state.active_language.as_str())
+                                    .with_device(device)
+                            }
+                            Err(err) => {
+                                let error = match err.kind {
+                                    audio::AudioErrorKind::DeviceNotFound => "device_not_found",
+                                    _ => "audio_error",
+                                };
+                                control_error_response(error, err.message)
+                            }
+                        }
+                    }
+                    ControlEvent::Devices => match deps.audio.describe_input_devices(&host) {
+                        Ok(devices) => {
+                            control_ok_response(state.status(), state.active_language.as_str())
+                                .with_devices(devices)
+                        }
+                        Err(err) => control_error_response("audio_error", err.message),
+                    },
                     ControlEvent::SetLanguage { language } => {
                         let normalized = normalize_language(&language);
-                        active_variant =
-                            model_pool.resolve_language(normalized.as_str(), config, deps)?;
-                        active_language = normalized;
+                        state.active_model =
+                            state
+                                .model_pool
+                                .resolve_language(normalized.as_str(), config, deps)?;
+                        state.active_language = normalized;
                         emit_model_loaded_event(
                             event_sender,
-                            &model_pool,
-                            &active_language,
-                            active_variant,
+                            &state.model_pool,
+                            &state.active_language,
+                            &state.active_model,
                         );
-                        let state = if recording { "recording" } else { "idle" };
-                        control_ok_response(state, active_language.as_str())
+                        control_ok_response(state.status(), state.active_language.as_str())
                     }
                     ControlEvent::Stop => {
                         shutdown.store(true, Ordering::Relaxed);
-                        control_ok_response(
-                            if recording { "recording" } else { "idle" },
-                            active_language.as_str(),
-                        )
+                        control_ok_response(state.status(), state.active_language.as_str())
                     }
                     ControlEvent::Error(error_message) => {
                         emit_daemon_event(
@@ -473,92 +996,646 @@ pub fn run_daemon_loop(
             }
         }
 
-        if recording {
-            if let Some(active) = capture.as_mut() {
-                active.drain(&mut buffer);
+        if !state.recording {
+            state
+                .model_pool
+                .unload_idle(Instant::now(), &state.active_model);
+            if let Some(active) = state.capture.as_mut() {
+                idle_samples.clear();
+                drain_conditioned(
+                    active.as_mut(),
+                    &mut state.conditioner,
+                    &mut state.raw,
+                    &mut idle_samples,
+                );
+                pre_roll.push(&idle_samples);
+            }
+        }
+
+        if state.recording {
+            if let Some(active) = state.capture.as_mut() {
+                drain_conditioned(
+                    active.as_mut(),
+                    &mut state.conditioner,
+                    &mut state.raw,
+                    &mut state.buffer,
+                );
+            }
+        }
+
+        let dropped = state
+            .capture
+            .as_mut()
+            .map_or(0, |active| active.take_overflow());
+        if dropped > 0 {
+            let device = input_device.label();
+            let dropped_ms = dropped as u64 * 1000 / u64::from(config.sample_rate.max(1));
+            output.stderr(&format!(
+                "audio overflow on {device}: dropped {dropped} samples ({dropped_ms} ms)"
+            ));
+            emit_daemon_event(
+                event_sender,
+                ipc::DaemonEventType::AudioOverflow {
+                    device,
+                    dropped_samples: dropped as u64,
+                    dropped_ms,
+                },
+            );
+        }
+
+        if let Some(reason) = state
+            .capture
+            .as_mut()
+            .and_then(|active| active.device_lost())
+        {
+            let device = input_device.label();
+            let message = format!("Input device lost: {device} ({reason})");
+            output.stderr(&message);
+            emit_daemon_event(event_sender, ipc::DaemonEventType::Error { message });
+            input_device.mark_lost(&device, reason, event_sender);
+            if state.recording {
+                stop_recording(
+                    &mut state,
+                    ipc::StopReason::DeviceLost,
+                    config,
+                    deps,
+                    &vad,
+                    output,
+                    event_sender,
+                )?;
+            }
+            state.capture = None;
+            pre_roll.take();
+        }
+
+        if state.recording
+            && state
+                .capture
+                .as_mut()
+                .is_some_and(|active| active.end_of_input())
+        {
+            output.stdout("End of input. Stopping recording...");
+            stop_recording(
+                &mut state,
+                ipc::StopReason::EndOfInput,
+                config,
+                deps,
+                &vad,
+                output,
+                event_sender,
+            )?;
+        }
+
+        if state.recording {
+            if level_samples > 0 && state.buffer.len() >= level_position + level_samples {
+                let window = &state.buffer[level_position..];
+                let level = audio::measure_level(window);
+                let speech = audio::analyze_speech(
+                    window,
+                    config.sample_rate,
+                    &vad,
+                    state.detector.as_mut(),
+                )
+                .map(|chunks| audio::contains_speech(&chunks))
+                .unwrap_or(false);
+                emit_daemon_event(
+                    event_sender,
+                    ipc::DaemonEventType::AudioLevel {
+                        rms_dbfs: level.rms_dbfs,
+                        peak_dbfs: level.peak_dbfs,
+                        speech,
+                        clipping: level.clipping,
+                    },
+                );
+                level_position = state.buffer.len();
+            }
+            if calibration_pending && state.buffer.len() >= calibration_samples {
+                calibration_pending = false;
+                if let Some(calibration) = audio::calibrate_from_silence(
+                    &state.buffer[..calibration_samples],
+                    config.sample_rate,
+                    &vad,
+                    state.detector.as_mut(),
+                )
+                .map_err(|err| AppError::audio(err.message))?
+                {
+                    state.detector.calibrate(&calibration);
+                    if vad.debug {
+                        output.stderr(&format!(
+                            "vad noise floor {:.4}; threshold {:.4}",
+                            calibration.noise_floor, calibration.threshold
+                        ));
+                    }
+                }
+            }
+            if auto_stop
+                && !calibration_pending
+                && silence_tracker
+                    .update(&state.buffer, state.detector.as_mut())
+                    .map_err(|err| AppError::audio(err.message))?
+            {
+                output.stdout("Silence detected. Stopping recording...");
+                stop_recording(
+                    &mut state,
+                    ipc::StopReason::SilenceTimeout,
+                    config,
+                    deps,
+                    &vad,
+                    output,
+                    event_sender,
+                )?;
             }
         }
     }
     Ok(())
 }
 
-fn stop_recording(
-    context: &mut RecordingContext<'_>,
-    capture: &mut Option<Box<dyn CaptureSource>>,
-    buffer: &mut Vec<f32>,
-    utterance_index: &mut u64,
-) -> Result<(), AppError> {
-    let mut active = capture
-        .take()
-        .ok_or_else(|| AppError::runtime("capture stream missing"))?;
-    active.drain(buffer);
-    finalize_recording(context, buffer, utterance_index)?;
-    Ok(())
+// Loop state shared by every path that starts or stops a recording.
+struct DaemonState {
+    model_pool: ModelPool,
+    detector: Box<dyn audio::VoiceActivityDetector>,
+    recording: bool,
+    active_language: String,
+    active_model: ModelSource,
+    capture: Option<Box<dyn CaptureSource>>,
+    vad_threshold: f32,
+    conditioner: preprocess::InputConditioner,
+    raw: Vec<f32>,
+    buffer: Vec<f32>,
+    utterance_index: u64,
 }
 
-struct RecordingContext<'a> {
-    transcriber: &'a dyn Transcriber,
-    config: &'a DaemonConfig,
-    language: &'a str,
-    vad: &'a audio::VadConfig,
-    output: &'a mut dyn DaemonOutput,
-    event_sender: Option<&'a mpsc::Sender<ipc::DaemonEvent>>,
+impl DaemonState {
+    fn status(&self) -> &'static str {
+        if self.recording {
+            "recording"
+        } else {
+            "idle"
+        }
+    }
 }
 
-fn finalize_recording(
-    context: &mut RecordingContext<'_>,
-    buffer: &[f32],
-    utterance_index: &mut u64,
-) -> Result<(), AppError> {
-    let trimmed = audio::trim_trailing_silence(buffer, context.config.sample_rate, context.vad);
-    if trimmed.is_empty() {
-        return Ok(());
+// Tracks the device recordings should use (`selected`, changed at runtime by
+// `set-device`) and the one capture actually opened, so the daemon can fall
+// back to the default device while the selected one is missing.
+#[derive(Default)]
+struct InputDevice {
+    selected: Option<String>,
+    active: Option<String>,
+    lost: bool,
+}
+
+impl InputDevice {
+    fn label(&self) -> String {
+        self.active
+            .clone()
+            .unwrap_or_else(|| DEFAULT_DEVICE_LABEL.to_string())
     }
-    *utterance_index += 1;
-    let duration_ms = audio::samples_to_ms(trimmed.len(), context.config.sample_rate);
-    if context.config.dump_audio {
-        dump_audio_samples(&trimmed, context.config.sample_rate, context.output)?;
+
+    fn selected_label(&self) -> String {
+        self.selected
+            .clone()
+            .unwrap_or_else(|| DEFAULT_DEVICE_LABEL.to_string())
     }
-    let transcript = context
-        .transcriber
-        .transcribe(&trimmed, Some(context.language))
-        .map_err(|err| {
+
+    fn select(&mut self, device: Option<String>) {
+        self.selected = device;
+        self.lost = false;
+    }
+
+    fn mark_lost(
+        &mut self,
+        device: &str,
+        message: String,
+        event_sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
+    ) {
+        if self.lost {
+            return;
+        }
+        self.lost = true;
+        emit_daemon_event(
+            event_sender,
+            ipc::DaemonEventType::DeviceLost {
+                device: device.to_string(),
+                message,
+            },
+        );
+    }
+
+    fn mark_open(
+        &mut self,
+        device: Option<&str>,
+        output: &mut dyn DaemonOutput,
+        event_sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
+    ) {
+        self.active = device.map(str::to_string);
+        if self.lost {
+            self.lost = false;
+            let device = self.label();
+            output.stdout(&format!("Input device restored: {device}"));
             emit_daemon_event(
-                context.event_sender,
-                ipc::DaemonEventType::Error {
-                    message: err.to_string(),
-                },
+                event_sender,
+                ipc::DaemonEventType::DeviceRestored { device },
             );
-            AppError::runtime(err.to_string())
-        })?;
-    emit_transcript(
-        context.config,
-        context.output,
-        &transcript,
-        audio::SegmentInfo {
-            index: *utterance_index,
-            duration_ms,
-        },
-    )
-    .map_err(AppError::runtime)?;
-    emit_daemon_event(
-        context.event_sender,
-        ipc::DaemonEventType::TranscriptFinal {
-            language: context.language.to_string(),
-            utterance: *utterance_index,
-            duration_ms,
-            text: transcript,
-        },
-    );
-    context.output.stdout("Ready for next utterance.");
-    Ok(())
+        }
+    }
 }
 
-fn emit_daemon_event(sender: Option<&mpsc::Sender<ipc::DaemonEvent>>, event: ipc::DaemonEventType) {
-    let Some(sender) = sender else {
-        return;
-    };
-    let event = ipc::DaemonEvent::new(Utc::now().to_rfc3339(), event);
+const DEFAULT_DEVICE_LABEL: &str = "default";
+
+fn open_capture(
+    config: &DaemonConfig,
+    deps: &DaemonDeps,
+    host: &cpal::Host,
+    input_device: &mut InputDevice,
+    output: &mut dyn DaemonOutput,
+    event_sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
+) -> Result<Box<dyn CaptureSource>, audio::AudioError> {
+    if let Some(device) = input_device.selected.clone().as_deref() {
+        match deps
+            .audio
+            .start_capture(host, Some(device), config.sample_rate)
+        {
+            Ok(capture) => {
+                report_capture_details(capture.as_ref(), output);
+                input_device.mark_open(Some(device), output, event_sender);
+                return Ok(capture);
+            }
+            Err(err) if config.device_fallback == DeviceFallback::Off => return Err(err),
+            Err(err) => {
+                output.stderr(&format!(
+                    "{}; falling back to the default input device.",
+                    err.message
+                ));
+                input_device.mark_lost(device, err.message, event_sender);
+            }
+        }
+    }
+
+    let capture = deps.audio.start_capture(host, None, config.sample_rate)?;
+    report_capture_details(capture.as_ref(), output);
+    // Falling back keeps the configured device marked lost so it is retried next time.
+    if input_device.selected.is_none() {
+        input_device.mark_open(None, output, event_sender);
+    } else {
+        input_device.active = None;
+    }
+    Ok(capture)
+}
+
+// Drains the capture through the input conditioning (DC filter, gain,
+// denoise), so everything downstream sees the same audio.
+fn drain_conditioned(
+    capture: &mut dyn CaptureSource,
+    conditioner: &mut preprocess::InputConditioner,
+    raw: &mut Vec<f32>,
+    output: &mut Vec<f32>,
+) {
+    raw.clear();
+    capture.drain(raw);
+    conditioner.process(raw, output);
+}
+
+// Looks up the calibrated threshold for the device capture actually opened, then
+// for the requested one, and otherwise falls back to the configured threshold.
+fn apply_device_threshold(
+    state: &mut DaemonState,
+    config: &DaemonConfig,
+    input_device: &InputDevice,
+    output: &mut dyn DaemonOutput,
+) {
+    if config.device_thresholds.is_empty() {
+        return;
+    }
+    let resolved = state
+        .capture
+        .as_ref()
+        .and_then(|capture| capture.device_id());
+    let calibrated = [resolved.as_deref(), input_device.active.as_deref()]
+        .into_iter()
+        .map(calibration::device_key)
+        .find_map(|key| {
+            config
+                .device_thresholds
+                .get(&key)
+                .map(|threshold| (key, *threshold))
+        });
+    let threshold = calibrated
+        .as_ref()
+        .map_or(config.vad_threshold, |(_, threshold)| *threshold);
+    state.detector.calibrate(&audio::NoiseCalibration {
+        noise_floor: threshold / audio::NOISE_FLOOR_MULTIPLIER,
+        threshold,
+    });
+    if threshold != state.vad_threshold {
+        state.vad_threshold = threshold;
+        match calibrated {
+            Some((key, _)) => output.stdout(&format!(
+                "VAD threshold: {threshold:.4} (calibrated for {key})"
+            )),
+            None => output.stdout(&format!("VAD threshold: {threshold:.4}")),
+        }
+    }
+}
+
+fn report_capture_details(capture: &dyn CaptureSource, output: &mut dyn DaemonOutput) {
+    for line in capture.details() {
+        output.stdout(&line);
+    }
+}
+
+// Also returns the device that was measured, so the result can be stored
+// under the name the daemon will see when it opens the same device.
+pub fn run_calibration(
+    config: &DaemonConfig,
+    deps: &DaemonDeps,
+    duration_ms: u64,
+) -> Result<(Option<String>, audio::NoiseCalibration), AppError> {
+    let host = select_audio_host(config.audio_host)?;
+    audio::configure_alsa_logging(config.debug_audio);
+    let vad = audio::VadConfig::new(
+        true,
+        config.vad_silence_ms,
+        config.vad_threshold,
+        config.vad_chunk_ms,
+        config.debug_vad,
+    );
+    let target = audio::ms_to_samples(duration_ms, config.sample_rate).max(1);
+    let mut capture = deps
+        .audio
+        .start_capture(&host, config.device.as_deref(), config.sample_rate)
+        .map_err(|err| AppError::audio(err.message))?;
+
+    // The threshold applies to conditioned audio, so measure the same.
+    let mut conditioner = preprocess::InputConditioner::new(
+        config.sample_rate,
+        config.input_gain_db,
+        config.dc_filter,
+        config.denoise,
+    );
+    let mut raw = Vec::new();
+
+    // Give a stalled device twice the requested time before giving up.
+    let poll = Duration::from_millis(20);
+    let max_polls = (duration_ms * 2 / 20).max(50);
+    let mut buffer = Vec::with_capacity(target);
+    for _ in 0..max_polls {
+        drain_conditioned(capture.as_mut(), &mut conditioner, &mut raw, &mut buffer);
+        if buffer.len() >= target {
+            break;
+        }
+        thread::sleep(poll);
+    }
+    if buffer.len() < target {
+        return Err(AppError::audio(format!(
+            "captured only {} ms of audio during calibration",
+            audio::samples_to_ms(buffer.len(), config.sample_rate)
+        )));
+    }
+
+    let calibration = audio::calibrate_noise_floor(&buffer[..target], config.sample_rate, &vad)
+        .ok_or_else(|| AppError::audio("no audio captured during calibration"))?;
+    Ok((capture.device_id(), calibration))
+}
+
+// Drains the rest of the capture, transcribes the recording and reports why
+// it ended. The capture stays open only with `warm_capture`.
+fn stop_recording(
+    state: &mut DaemonState,
+    reason: ipc::StopReason,
+    config: &DaemonConfig,
+    deps: &DaemonDeps,
+    vad: &audio::VadConfig,
+    output: &mut dyn DaemonOutput,
+    event_sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
+) -> Result<(), AppError> {
+    state.recording = false;
+    let active = state
+        .capture
+        .as_mut()
+        .ok_or_else(|| AppError::runtime("capture stream missing"))?;
+    drain_conditioned(
+        active.as_mut(),
+        &mut state.conditioner,
+        &mut state.raw,
+        &mut state.buffer,
+    );
+    state.conditioner.flush(&mut state.buffer);
+    if !config.warm_capture {
+        state.capture = None;
+    }
+    let transcriber = active_transcriber(
+        &mut state.model_pool,
+        &state.active_model,
+        &state.active_language,
+        config,
+        deps,
+    )?;
+    let mut context = RecordingContext {
+        transcriber,
+        config,
+        language: state.active_language.as_str(),
+        vad,
+        detector: state.detector.as_mut(),
+        clipping: state.conditioner.take_clipping(),
+        output,
+        event_sender,
+    };
+    finalize_recording(&mut context, &state.buffer, &mut state.utterance_index)?;
+    emit_daemon_event(
+        event_sender,
+        ipc::DaemonEventType::RecordingStopped {
+            language: state.active_language.clone(),
+            reason,
+        },
+    );
+    Ok(())
+}
+
+struct RecordingContext<'a> {
+    transcriber: &'a dyn Transcriber,
+    config: &'a DaemonConfig,
+    language: &'a str,
+    vad: &'a audio::VadConfig,
+    detector: &'a mut dyn audio::VoiceActivityDetector,
+    clipping: Option<preprocess::ClippingReport>,
+    output: &'a mut dyn DaemonOutput,
+    event_sender: Option<&'a mpsc::Sender<ipc::DaemonEvent>>,
+}
+
+fn finalize_recording(
+    context: &mut RecordingContext<'_>,
+    buffer: &[f32],
+    utterance_index: &mut u64,
+) -> Result<(), AppError> {
+    let sample_rate = context.config.sample_rate;
+    let chunks = if context.vad.enabled {
+        audio::analyze_speech(buffer, sample_rate, context.vad, context.detector)
+            .map_err(|err| AppError::audio(err.message))?
+    } else {
+        Vec::new()
+    };
+    if context.vad.debug {
+        report_vad_chunks(
+            context.output,
+            context.detector.name(),
+            &chunks,
+            sample_rate,
+        );
+    }
+    if context.vad.enabled && !audio::contains_speech(&chunks) {
+        context
+            .output
+            .stdout("No speech detected; skipping transcription.");
+        emit_daemon_event(
+            context.event_sender,
+            ipc::DaemonEventType::NoSpeech {
+                language: context.language.to_string(),
+                duration_ms: audio::samples_to_ms(buffer.len(), sample_rate),
+            },
+        );
+        return Ok(());
+    }
+    let trimmed = audio::trim_trailing_silence(buffer, sample_rate, context.vad, &chunks);
+    let mut trimmed = audio::trim_leading_silence(&trimmed, context.vad, &chunks);
+    if trimmed.is_empty() {
+        return Ok(());
+    }
+    if let Some(report) = context.clipping {
+        context.output.stderr(&format!(
+            "warning: {} samples clipped; lower the input level or input_gain_db",
+            report.clipped_samples
+        ));
+        emit_daemon_event(
+            context.event_sender,
+            ipc::DaemonEventType::ClippingDetected {
+                language: context.language.to_string(),
+                clipped_samples: report.clipped_samples as u64,
+                gain_db: context.config.input_gain_db,
+            },
+        );
+    }
+    let normalize_gain_db = preprocess::normalize(&mut trimmed, context.config.normalize);
+    if context.config.debug_audio && context.config.normalize != NormalizeMode::Off {
+        context
+            .output
+            .stderr(&format!("normalized by {normalize_gain_db:+.1} dB"));
+    }
+    *utterance_index += 1;
+    let duration_ms = audio::samples_to_ms(trimmed.len(), context.config.sample_rate);
+    if context.config.dump_audio {
+        dump_audio_samples(&trimmed, context.config.sample_rate, context.output)?;
+    }
+    let transcript = context
+        .transcriber
+        .transcribe(&trimmed, Some(context.language))
+        .map_err(|err| {
+            emit_daemon_event(
+                context.event_sender,
+                ipc::DaemonEventType::Error {
+                    message: err.to_string(),
+                },
+            );
+            AppError::runtime(err.to_string())
+        })?;
+    emit_transcript(
+        context.config,
+        context.output,
+        &transcript,
+        audio::SegmentInfo {
+            index: *utterance_index,
+            duration_ms,
+        },
+    )
+    .map_err(AppError::runtime)?;
+    emit_daemon_event(
+        context.event_sender,
+        ipc::DaemonEventType::TranscriptFinal {
+            language: context.language.to_string(),
+            utterance: *utterance_index,
+            duration_ms,
+            text: transcript,
+        },
+    );
+    context.output.stdout("Ready for next utterance.");
+    Ok(())
+}
+
+fn report_vad_chunks(
+    output: &mut dyn DaemonOutput,
+    backend: &str,
+    chunks: &[audio::VadChunk],
+    sample_rate: u32,
+) {
+    for chunk in chunks {
+        let mut line = format!(
+            "vad[{backend}] {} ms: score={:.4} speech={}",
+            audio::samples_to_ms(chunk.start, sample_rate),
+            chunk.score,
+            chunk.speech
+        );
+        if !chunk.frame_probabilities.is_empty() {
+            let frames: Vec<String> = chunk
+                .frame_probabilities
+                .iter()
+                .map(|probability| format!("{probability:.2}"))
+                .collect();
+            line.push_str(&format!(" frames=[{}]", frames.join(", ")));
+        }
+        output.stderr(&line);
+    }
+}
+
+struct WhisperVad {
+    context: WhisperVadContext,
+    threshold: f32,
+}
+
+impl audio::VoiceActivityDetector for WhisperVad {
+    fn name(&self) -> &'static str {
+        "silero"
+    }
+
+    fn analyze(
+        &mut self,
+        samples: &[f32],
+        chunk_samples: usize,
+    ) -> Result<Vec<audio::VadChunk>, audio::AudioError> {
+        let probabilities = self
+            .context
+            .speech_probabilities(samples)
+            .map_err(|err| audio::AudioError::new(audio::AudioErrorKind::Vad, err.to_string()))?;
+        Ok(audio::chunks_from_frame_probabilities(
+            samples.len(),
+            chunk_samples,
+            whisper::VAD_FRAME_SAMPLES,
+            &probabilities,
+            self.threshold,
+        ))
+    }
+}
+
+fn create_voice_detector(
+    config: &DaemonConfig,
+    vad: &audio::VadConfig,
+) -> Result<Box<dyn audio::VoiceActivityDetector>, AppError> {
+    if !vad.enabled || config.vad_backend == VadBackend::Energy {
+        return Ok(Box::new(audio::EnergyVad::new(vad.energy_threshold)));
+    }
+    let prepared = model::prepare_vad_model(config.vad_model.as_deref(), config.download_model)?;
+    let context = WhisperVadContext::from_file(&prepared.path)
+        .map_err(|err| AppError::runtime(err.to_string()))?;
+    Ok(Box::new(WhisperVad {
+        context,
+        threshold: config.vad_probability,
+    }))
+}
+
+fn emit_daemon_event(sender: Option<&mpsc::Sender<ipc::DaemonEvent>>, event: ipc::DaemonEventType) {
+    let Some(sender) = sender else {
+        return;
+    };
+    let event = ipc::DaemonEvent::new(Utc::now().to_rfc3339(), event);
     let _ = sender.send(event);
 }
 
@@ -566,9 +1643,9 @@ fn emit_model_loaded_event(
     sender: Option<&mpsc::Sender<ipc::DaemonEvent>>,
     model_pool: &ModelPool,
     language: &str,
-    variant: ModelLanguage,
+    source: &ModelSource,
 ) {
-    let Some((model_size, model_language)) = model_pool.metadata_for_variant(variant) else {
+    let Some((model_size, model_language)) = model_pool.metadata_for(source) else {
         return;
     };
     emit_daemon_event(
@@ -577,18 +1654,31 @@ fn emit_model_loaded_event(
             language: language.to_string(),
             model_size: model_size.to_string(),
             model_language: model_language.to_string(),
+            backend: model_pool.backend_for(source).map(str::to_string),
         },
     );
 }
 
-fn active_transcriber(
-    model_pool: &ModelPool,
-    variant: ModelLanguage,
-) -> Result<&dyn Transcriber, AppError> {
-    model_pool.transcriber_for_variant(variant).ok_or_else(|| {
+// Reloads the active model if it was unloaded over the memory budget.
+fn active_transcriber<'a>(
+    model_pool: &'a mut ModelPool,
+    source: &ModelSource,
+    language: &str,
+    config: &DaemonConfig,
+    deps: &DaemonDeps,
+) -> Result<&'a dyn Transcriber, AppError> {
+    if model_pool.ensure_loaded(source, language, config, deps)? {
+        emit_model_loaded_event(
+            model_pool.event_sender.as_ref(),
+            model_pool,
+            language,
+            source,
+        );
+    }
+    model_pool.transcriber_for(source).ok_or_else(|| {
         AppError::runtime(format!(
-            "no transcriber loaded for model variant '{}'",
-            model_language_token(variant)
+            "no transcriber loaded for model '{}'",
+            source.name()
         ))
     })
 }
@@ -731,18 +1821,24 @@ pub fn start_events_socket_listener(
     let guard = SocketGuard {
         path: socket_path.to_path_buf(),
     };
-    let (event_sender, event_receiver) = mpsc::channel();
+    let (event_sender, event_receiver) = mpsc::channel::<ipc::DaemonEvent>();
 
     thread::spawn(move || {
-        let mut subscribers: Vec<UnixStream> = Vec::new();
+        let mut subscribers: Vec<EventSubscriber> = Vec::new();
         loop {
             accept_event_subscribers(&listener, &mut subscribers);
+            for subscriber in &mut subscribers {
+                subscriber.poll_requests();
+            }
             match event_receiver.recv_timeout(Duration::from_millis(20)) {
                 Ok(event) => {
                     let Ok(line) = ipc::to_json_line(&event) else {
                         continue;
                     };
-                    subscribers.retain_mut(|stream| stream.write_all(line.as_bytes()).is_ok());
+                    subscribers.retain_mut(|subscriber| {
+                        !subscriber.wants(&event.event)
+                            || subscriber.stream.write_all(line.as_bytes()).is_ok()
+                    });
                 }
                 Err(RecvTimeoutError::Timeout) => {}
                 Err(RecvTimeoutError::Disconnected) => break,
@@ -753,12 +1849,78 @@ pub fn start_events_socket_listener(
     Ok((guard, event_sender))
 }
 
-fn accept_event_subscribers(listener: &UnixListener, subscribers: &mut Vec<UnixStream>) {
+struct EventSubscriber {
+    stream: UnixStream,
+    opt_in: Vec<String>,
+    request: Vec<u8>,
+    reading: bool,
+}
+
+impl EventSubscriber {
+    fn new(stream: UnixStream) -> Self {
+        Self {
+            stream,
+            opt_in: Vec::new(),
+            request: Vec::new(),
+            reading: true,
+        }
+    }
+
+    fn wants(&self, event: &ipc::DaemonEventType) -> bool {
+        let name = event.name();
+        !ipc::OPT_IN_EVENTS.contains(&name) || self.opt_in.iter().any(|opt_in| opt_in == name)
+    }
+
+    // Reads are polled without blocking; writes keep the blocking timeout so slow
+    // subscribers are still dropped.
+    fn poll_requests(&mut self) {
+        if !self.reading {
+            return;
+        }
+        if self.stream.set_nonblocking(true).is_err() {
+            self.reading = false;
+            return;
+        }
+        let mut chunk = [0u8; 256];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    self.reading = false;
+                    break;
+                }
+                Ok(read) => self.request.extend_from_slice(&chunk[..read]),
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(_) => {
+                    self.reading = false;
+                    break;
+                }
+            }
+        }
+        let _ = self.stream.set_nonblocking(false);
+
+        while let Some(end) = self.request.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.request.drain(..=end).collect();
+            let Ok(events) = ipc::parse_subscribe_request(&String::from_utf8_lossy(&line)) else {
+                continue;
+            };
+            for event in events {
+                if !self.opt_in.contains(&event) {
+                    self.opt_in.push(event);
+                }
+            }
+        }
+        if self.request.len() > 1024 {
+            self.request.clear();
+        }
+    }
+}
+
+fn accept_event_subscribers(listener: &UnixListener, subscribers: &mut Vec<EventSubscriber>) {
     loop {
         match listener.accept() {
             Ok((stream, _addr)) => {
                 let _ = stream.set_write_timeout(Some(Duration::from_millis(50)));
-                subscribers.push(stream);
+                subscribers.push(EventSubscriber::new(stream));
             }
             Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => break,
             Err(_) => break,
@@ -896,6 +2058,8 @@ fn control_event_from_command(command: &str) -> Result<ControlEvent, String> {
     match request.command {
         ipc::ControlCommand::Toggle { lang } => Ok(ControlEvent::Toggle { language: lang }),
         ipc::ControlCommand::Status => Ok(ControlEvent::Status),
+        ipc::ControlCommand::Devices => Ok(ControlEvent::Devices),
+        ipc::ControlCommand::SetDevice { device } => Ok(ControlEvent::SetDevice { device }),
         ipc::ControlCommand::SetLanguage { lang } => {
             Ok(ControlEvent::SetLanguage { language: lang })
         }
@@ -937,6 +2101,14 @@ pub fn send_status_command() -> Result<ipc::ControlResponse, AppError> {
     send_daemon_command("status")
 }
 
+pub fn send_devices_command() -> Result<ipc::ControlResponse, AppError> {
+    send_daemon_command("devices")
+}
+
+pub fn send_set_device_command(device: &str) -> Result<ipc::ControlResponse, AppError> {
+    send_daemon_command(&format!("set-device device={device}"))
+}
+
 pub fn send_set_language_command(language: &str) -> Result<ipc::ControlResponse, AppError> {
     send_daemon_command(&format!("set-language lang={language}"))
 }
@@ -990,17 +2162,6 @@ fn send_daemon_command(command: &str) -> Result<ipc::ControlResponse, AppError>
     Ok(response)
 }
 
-fn model_size_token(size: ModelSize) -> &'static str {
-    match size {
-        ModelSize::Auto => "auto",
-        ModelSize::Tiny => "tiny",
-        ModelSize::Base => "base",
-        ModelSize::Small => "small",
-        ModelSize::Medium => "medium",
-        ModelSize::Large => "large",
-    }
-}
-
 fn model_language_token(model_language: ModelLanguage) -> &'static str {
     match model_language {
         ModelLanguage::Auto => "auto",
@@ -1008,20 +2169,36 @@ fn model_language_token(model_language: ModelLanguage) -> &'static str {
     }
 }
 
-struct CpalAudioBackend;
+struct CpalAudioBackend {
+    resample_quality: ResampleQuality,
+    channel: ChannelSelection,
+}
 
 impl AudioBackend for CpalAudioBackend {
     fn list_input_devices(&self, host: &cpal::Host) -> Result<Vec<String>, audio::AudioError> {
         audio::list_input_devices(host)
     }
 
+    fn describe_input_devices(
+        &self,
+        host: &cpal::Host,
+    ) -> Result<Vec<audio::InputDeviceInfo>, audio::AudioError> {
+        audio::describe_input_devices(host)
+    }
+
     fn start_capture(
         &self,
         host: &cpal::Host,
         device_name: Option<&str>,
         sample_rate: u32,
     ) -> Result<Box<dyn CaptureSource>, audio::AudioError> {
-        let capture = audio::start_capture(host, device_name, sample_rate)?;
+        let capture = audio::start_capture(
+            host,
+            device_name,
+            sample_rate,
+            self.resample_quality,
+            self.channel,
+        )?;
         Ok(Box::new(CpalCapture { inner: capture }))
     }
 }
@@ -1034,26 +2211,68 @@ impl CaptureSource for CpalCapture {
     fn drain(&mut self, output: &mut Vec<f32>) {
         audio::drain_samples(&mut self.inner, output);
     }
+
+    fn device_lost(&mut self) -> Option<String> {
+        audio::stream_error(&self.inner)
+    }
+
+    fn take_overflow(&mut self) -> usize {
+        audio::take_overflow(&self.inner)
+    }
+
+    fn details(&self) -> Vec<String> {
+        audio::capture_details(&self.inner).to_vec()
+    }
+
+    fn device_id(&self) -> Option<String> {
+        Some(audio::capture_device(&self.inner).to_string())
+    }
 }
 
-struct WhisperFactory;
+struct WhisperFactory {
+    verify_checksum: bool,
+    options: whisper::ContextOptions,
+}
 
 impl TranscriberFactory for WhisperFactory {
     fn load(
         &self,
-        spec: &ModelSpec,
+        source: &ModelSource,
         allow_download: bool,
+        progress: &mut dyn FnMut(model::DownloadProgress),
     ) -> Result<Box<dyn Transcriber>, AppError> {
-        let prepared = model::prepare_model(None, spec, allow_download)?;
+        let prepared = model::prepare_model_with_progress(source, allow_download, progress)?;
         let model_path = prepared.path;
-        let context = WhisperContext::from_file(&model_path)
+        // Fresh downloads were already checked against the manifest; custom
+        // files have no published digest.
+        if let ModelSource::Published(spec) = source {
+            if self.verify_checksum && !prepared.downloaded {
+                model::verify_checksum(&model_path, &spec.filename())?;
+            }
+        }
+        // The weights dominate the context size, so the file size is a fair estimate.
+        let memory_bytes = fs::metadata(&model_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        let context = WhisperContext::from_file_with_options(&model_path, self.options)
             .map_err(|err| AppError::runtime(err.to_string()))?;
-        Ok(Box::new(WhisperTranscriber { context }))
+        Ok(Box::new(WhisperTranscriber {
+            context,
+            memory_bytes,
+        }))
+    }
+
+    fn gpu_backend(&self) -> Option<String> {
+        if !self.options.use_gpu {
+            return None;
+        }
+        whisper::gpu_device_name(self.options.gpu_device)
     }
 }
 
 struct WhisperTranscriber {
     context: WhisperContext,
+    memory_bytes: u64,
 }
 
 impl Transcriber for WhisperTranscriber {
@@ -1062,11 +2281,20 @@ impl Transcriber for WhisperTranscriber {
             .transcribe(samples, language)
             .map_err(|err| AppError::runtime(err.to_string()))
     }
+
+    fn memory_bytes(&self) -> u64 {
+        self.memory_bytes
+    }
+
+    fn backend(&self) -> Option<&str> {
+        Some(self.context.backend())
+    }
 }
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod test_support {
     use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
     use std::sync::mpsc;
     use std::sync::{Arc, Mutex};
 
@@ -1076,7 +2304,7 @@ pub mod test_support {
     };
     use crate::audio::{AudioError, AudioErrorKind};
     use crate::error::AppError;
-    use crate::model::ModelSpec;
+    use crate::model::{DownloadProgress, ModelSource};
 
     #[derive(Default)]
     pub struct TestOutput {
@@ -1108,6 +2336,8 @@ pub mod test_support {
         devices: Vec<String>,
         chunks: Arc<Mutex<VecDeque<Vec<f32>>>>,
         start_error: Arc<Mutex<Option<AudioError>>>,
+        connected: Arc<AtomicBool>,
+        overflow: Arc<AtomicUsize>,
     }
 
     impl TestAudioBackend {
@@ -1116,6 +2346,8 @@ pub mod test_support {
                 devices,
                 chunks: Arc::new(Mutex::new(chunks.into())),
                 start_error: Arc::new(Mutex::new(None)),
+                connected: Arc::new(AtomicBool::new(true)),
+                overflow: Arc::new(AtomicUsize::new(0)),
             }
         }
 
@@ -1124,8 +2356,21 @@ pub mod test_support {
                 devices,
                 chunks: Arc::new(Mutex::new(VecDeque::new())),
                 start_error: Arc::new(Mutex::new(Some(error))),
+                connected: Arc::new(AtomicBool::new(true)),
+                overflow: Arc::new(AtomicUsize::new(0)),
             }
         }
+
+        // Clearing the flag unplugs the named devices: open captures report loss
+        // and only the default device can be opened until it is set again.
+        pub fn connection(&self) -> Arc<AtomicBool> {
+            Arc::clone(&self.connected)
+        }
+
+        // Samples added here are reported once as dropped by the open capture.
+        pub fn overflow(&self) -> Arc<AtomicUsize> {
+            Arc::clone(&self.overflow)
+        }
     }
 
     impl AudioBackend for TestAudioBackend {
@@ -1141,33 +2386,40 @@ pub mod test_support {
 
         fn start_capture(
             &self,
-            _host: &cpal::Host,
+            host: &cpal::Host,
             device_name: Option<&str>,
             _sample_rate: u32,
         ) -> Result<Box<dyn CaptureSource>, AudioError> {
             if let Some(err) = self.start_error.lock().expect("audio error lock").take() {
                 return Err(err);
             }
-            if let Some(device) = device_name {
-                if !self
-                    .devices
-                    .iter()
-                    .any(|name| name.eq_ignore_ascii_case(device))
-                {
-                    return Err(AudioError {
-                        kind: AudioErrorKind::DeviceNotFound,
-                        message: format!("input device not found: {device}"),
-                    });
+            let device = match device_name {
+                Some(device) => {
+                    if !self.connected.load(Ordering::SeqCst) {
+                        return Err(AudioError {
+                            kind: AudioErrorKind::DeviceNotFound,
+                            message: format!("input device not found: {device}"),
+                        });
+                    }
+                    let host = crate::audio::host_name(host);
+                    self.devices[crate::audio::find_device(&host, &self.devices, device)?].clone()
                 }
-            }
+                None => "default".to_string(),
+            };
             Ok(Box::new(TestCapture {
+                device,
                 chunks: Arc::clone(&self.chunks),
+                connected: device_name.map(|_| Arc::clone(&self.connected)),
+                overflow: Arc::clone(&self.overflow),
             }))
         }
     }
 
     struct TestCapture {
+        device: String,
         chunks: Arc<Mutex<VecDeque<Vec<f32>>>>,
+        connected: Option<Arc<AtomicBool>>,
+        overflow: Arc<AtomicUsize>,
     }
 
     impl CaptureSource for TestCapture {
@@ -1176,13 +2428,36 @@ pub mod test_support {
                 output.extend(chunk);
             }
         }
+
+        fn device_lost(&mut self) -> Option<String> {
+            match &self.connected {
+                Some(connected) if !connected.load(Ordering::SeqCst) => {
+                    Some("device disconnected".to_string())
+                }
+                _ => None,
+            }
+        }
+
+        fn take_overflow(&mut self) -> usize {
+            self.overflow.swap(0, Ordering::SeqCst)
+        }
+
+        fn details(&self) -> Vec<String> {
+            vec![format!("Selected input device: {}", self.device)]
+        }
+
+        fn device_id(&self) -> Option<String> {
+            Some(self.device.clone())
+        }
     }
 
     #[derive(Clone)]
     pub struct TestTranscriberFactory {
         responses: Arc<Mutex<VecDeque<Result<String, AppError>>>>,
-        loaded_specs: Arc<Mutex<Vec<(ModelSpec, bool)>>>,
+        loaded_specs: Arc<Mutex<Vec<(ModelSource, bool)>>>,
         transcribe_languages: Arc<Mutex<Vec<Option<String>>>>,
+        model_bytes: u64,
+        backend: Option<String>,
     }
 
     impl TestTranscriberFactory {
@@ -1192,6 +2467,8 @@ pub mod test_support {
                 responses: Arc::new(Mutex::new(responses)),
                 loaded_specs: Arc::new(Mutex::new(Vec::new())),
                 transcribe_languages: Arc::new(Mutex::new(Vec::new())),
+                model_bytes: 0,
+                backend: None,
             }
         }
 
@@ -1200,15 +2477,29 @@ pub mod test_support {
                 responses: Arc::new(Mutex::new(responses.into())),
                 loaded_specs: Arc::new(Mutex::new(Vec::new())),
                 transcribe_languages: Arc::new(Mutex::new(Vec::new())),
+                model_bytes: 0,
+                backend: None,
             }
         }
 
-        pub fn load_count(&self) -> usize {
-            self.loaded_specs.lock().expect("loaded specs lock").len()
+        // Size every loaded model reports for memory budget accounting.
+        pub fn with_model_bytes(mut self, model_bytes: u64) -> Self {
+            self.model_bytes = model_bytes;
+            self
         }
 
-        pub fn loaded_specs(&self) -> Vec<(ModelSpec, bool)> {
-            self.loaded_specs.lock().expect("loaded specs lock").clone()
+        // Inference backend every loaded model reports.
+        pub fn with_backend(mut self, backend: &str) -> Self {
+            self.backend = Some(backend.to_string());
+            self
+        }
+
+        pub fn load_count(&self) -> usize {
+            self.loaded_specs.lock().expect("loaded specs lock").len()
+        }
+
+        pub fn loaded_specs(&self) -> Vec<(ModelSource, bool)> {
+            self.loaded_specs.lock().expect("loaded specs lock").clone()
         }
 
         pub fn transcribed_languages(&self) -> Vec<Option<String>> {
@@ -1222,23 +2513,32 @@ pub mod test_support {
     impl TranscriberFactory for TestTranscriberFactory {
         fn load(
             &self,
-            spec: &ModelSpec,
+            source: &ModelSource,
             allow_download: bool,
+            _progress: &mut dyn FnMut(DownloadProgress),
         ) -> Result<Box<dyn Transcriber>, AppError> {
             self.loaded_specs
                 .lock()
                 .expect("loaded specs lock")
-                .push((*spec, allow_download));
+                .push((source.clone(), allow_download));
             Ok(Box::new(TestTranscriber {
                 responses: Arc::clone(&self.responses),
                 transcribe_languages: Arc::clone(&self.transcribe_languages),
+                model_bytes: self.model_bytes,
+                backend: self.backend.clone(),
             }))
         }
+
+        fn gpu_backend(&self) -> Option<String> {
+            self.backend.clone().filter(|backend| backend != "cpu")
+        }
     }
 
     struct TestTranscriber {
         responses: Arc<Mutex<VecDeque<Result<String, AppError>>>>,
         transcribe_languages: Arc<Mutex<Vec<Option<String>>>>,
+        model_bytes: u64,
+        backend: Option<String>,
     }
 
     impl Transcriber for TestTranscriber {
@@ -1257,6 +2557,14 @@ pub mod test_support {
                 None => Ok(String::new()),
             }
         }
+
+        fn memory_bytes(&self) -> u64 {
+            self.model_bytes
+        }
+
+        fn backend(&self) -> Option<&str> {
+            self.backend.as_deref()
+        }
     }
 
     pub fn control_channel() -> (mpsc::Sender<ControlMessage>, mpsc::Receiver<ControlMessage>) {
@@ -1274,6 +2582,7 @@ pub mod test_support {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{ModelSize, ModelSpec};
     use std::env;
     use std::fs;
     use std::io::{Read, Write};
@@ -1377,19 +2686,45 @@ mod tests {
             transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Small,
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "en".to_string(),
             model_variants: ModelVariants::Both,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
@@ -1433,19 +2768,45 @@ mod tests {
             transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Small,
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "en".to_string(),
             model_variants: ModelVariants::Both,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
@@ -1557,6 +2918,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn event_fanout_sends_opt_in_events_only_to_subscribed_clients() -> Result<(), AppError> {
+        let _lock = lock_tests();
+        let runtime_dir = temp_runtime_dir();
+        fs::create_dir_all(&runtime_dir)
+            .map_err(|err| AppError::runtime(format!("failed to create runtime dir: {err}")))?;
+        let _env_guard = EnvGuard::set("XDG_RUNTIME_DIR", &runtime_dir);
+
+        let socket_path = daemon_events_socket_path()?;
+        let (_socket_guard, event_sender) = start_events_socket_listener(&socket_path)?;
+
+        let mut meter = UnixStream::connect(&socket_path)
+            .map_err(|err| AppError::runtime(format!("failed to connect meter client: {err}")))?;
+        let mut plain = UnixStream::connect(&socket_path)
+            .map_err(|err| AppError::runtime(format!("failed to connect plain client: {err}")))?;
+        meter
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .map_err(|err| AppError::runtime(format!("failed to set meter timeout: {err}")))?;
+        plain
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .map_err(|err| AppError::runtime(format!("failed to set plain timeout: {err}")))?;
+        meter
+            .write_all(b"subscribe audio_level\n")
+            .map_err(|err| AppError::runtime(format!("failed to subscribe: {err}")))?;
+
+        thread::sleep(Duration::from_millis(80));
+
+        let level = ipc::DaemonEvent::new(
+            "2026-02-06T10:00:00Z",
+            ipc::DaemonEventType::AudioLevel {
+                rms_dbfs: -32.5,
+                peak_dbfs: -12.0,
+                speech: true,
+                clipping: false,
+            },
+        );
+        let ready =
+            ipc::DaemonEvent::new("2026-02-06T10:00:01Z", ipc::DaemonEventType::DaemonReady);
+        for event in [&level, &ready] {
+            event_sender
+                .send(event.clone())
+                .map_err(|err| AppError::runtime(format!("failed to send event: {err}")))?;
+        }
+
+        let meter_first = ipc::from_json_line::<ipc::DaemonEvent>(&read_event_line(&mut meter)?)
+            .map_err(|err| AppError::runtime(format!("failed to parse meter event: {err}")))?;
+        let plain_first = ipc::from_json_line::<ipc::DaemonEvent>(&read_event_line(&mut plain)?)
+            .map_err(|err| AppError::runtime(format!("failed to parse plain event: {err}")))?;
+
+        assert_eq!(meter_first, level);
+        assert_eq!(plain_first, ready);
+        Ok(())
+    }
+
     #[test]
     fn event_fanout_drops_disconnected_clients_and_keeps_healthy_clients() -> Result<(), AppError> {
         let _lock = lock_tests();
@@ -1590,6 +3005,7 @@ mod tests {
             "2026-02-06T10:00:02Z",
             ipc::DaemonEventType::RecordingStopped {
                 language: "fr".to_string(),
+                reason: ipc::StopReason::Toggle,
             },
         );
         event_sender
@@ -1620,31 +3036,63 @@ mod tests {
             transcriber_factory: Box::new(transcriber_factory.clone()),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Small,
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "sv".to_string(),
             model_variants: ModelVariants::Both,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
         };
 
-        let model_pool = ModelPool::preload(&config, &deps)?;
+        let model_pool = ModelPool::preload(&config, &deps, None)?;
 
         assert!(model_pool
-            .transcriber_for_variant(ModelLanguage::En)
+            .transcriber_for(&ModelSource::Published(ModelSpec::new(
+                ModelSize::Small,
+                ModelLanguage::En
+            )))
             .is_some());
         assert!(model_pool
-            .transcriber_for_variant(ModelLanguage::Auto)
+            .transcriber_for(&ModelSource::Published(ModelSpec::new(
+                ModelSize::Small,
+                ModelLanguage::Auto
+            )))
             .is_some());
         assert_eq!(transcriber_factory.load_count(), 2);
         Ok(())
@@ -1658,185 +3106,508 @@ mod tests {
             transcriber_factory: Box::new(transcriber_factory.clone()),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Medium,
+            model_size: ModelSize::Medium.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "en".to_string(),
             model_variants: ModelVariants::Both,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
         };
 
-        let _model_pool = ModelPool::preload(&config, &deps)?;
+        let _model_pool = ModelPool::preload(&config, &deps, None)?;
         let loaded_specs = transcriber_factory.loaded_specs();
 
         assert!(!loaded_specs.is_empty());
-        assert!(loaded_specs
-            .iter()
-            .all(|(spec, _)| spec.size == ModelSize::Medium));
+        assert!(loaded_specs.iter().all(|(source, _)| matches!(
+            source,
+            ModelSource::Published(spec) if spec.size == ModelSize::Medium
+        )));
         Ok(())
     }
 
     #[test]
-    fn model_pool_allows_english_with_multilingual_variant_only() -> Result<(), AppError> {
+    fn model_pool_loads_custom_model_aliases() -> Result<(), AppError> {
         let transcriber_factory = TestTranscriberFactory::new(Vec::new());
         let deps = DaemonDeps {
             audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
             transcriber_factory: Box::new(transcriber_factory.clone()),
         };
-        let config = DaemonConfig {
-            model_size: ModelSize::Small,
+        let mut config = DaemonConfig {
+            model_size: ModelRef::Alias("work".to_string()),
+            models: HashMap::from([(
+                "work".to_string(),
+                PathBuf::from("/opt/models/ggml-work.bin"),
+            )]),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "en".to_string(),
-            model_variants: ModelVariants::Multilingual,
+            model_variants: ModelVariants::En,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
         };
 
-        let mut model_pool = ModelPool::preload(&config, &deps)?;
-        let resolved = model_pool.resolve_language("en", &config, &deps)?;
-        assert_eq!(resolved, ModelLanguage::Auto);
-        assert_eq!(transcriber_factory.load_count(), 1);
+        let model_pool = ModelPool::preload(&config, &deps, None)?;
+
+        assert_eq!(
+            transcriber_factory.loaded_specs(),
+            vec![(
+                ModelSource::Custom {
+                    name: "work".to_string(),
+                    path: PathBuf::from("/opt/models/ggml-work.bin"),
+                },
+                false
+            )]
+        );
+        assert_eq!(
+            model_pool.metadata_for(&ModelSource::Custom {
+                name: "work".to_string(),
+                path: PathBuf::from("/opt/models/ggml-work.bin"),
+            }),
+            Some(("work", "en"))
+        );
+
+        config.model_size = ModelRef::Alias("home".to_string());
+        let err = ModelPool::preload(&config, &deps, None)
+            .err()
+            .expect("unknown alias should fail");
+        assert!(err.to_string().contains("configured aliases: work"));
         Ok(())
     }
 
     #[test]
-    fn model_pool_rejects_non_english_with_en_only_variant() {
+    fn model_pool_maps_languages_to_their_own_models() -> Result<(), AppError> {
         let transcriber_factory = TestTranscriberFactory::new(Vec::new());
         let deps = DaemonDeps {
             audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
-            transcriber_factory: Box::new(transcriber_factory),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Small,
-            model_variants: ModelVariants::En,
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::from([
+                (
+                    "en".to_string(),
+                    "small.en".parse::<ModelRef>().expect("model"),
+                ),
+                ("sv".to_string(), ModelSize::Medium.into()),
+            ]),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
-            language: "sv".to_string(),
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::En,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
         };
 
-        let err = ModelPool::preload(&config, &deps)
-            .err()
-            .expect("expected config error");
-        assert!(err
-            .to_string()
-            .contains("no compatible model variant configured"));
+        let small_en = ModelSource::Published(ModelSpec::new(ModelSize::Small, ModelLanguage::En));
+        let medium = ModelSource::Published(ModelSpec::new(ModelSize::Medium, ModelLanguage::Auto));
+
+        let mut model_pool = ModelPool::preload(&config, &deps, None)?;
+        assert_eq!(transcriber_factory.load_count(), 1);
+
+        assert_eq!(model_pool.resolve_language("sv", &config, &deps)?, medium);
+        assert_eq!(model_pool.metadata_for(&medium), Some(("medium", "auto")));
+        assert_eq!(model_pool.resolve_language("en", &config, &deps)?, small_en);
+        assert!(model_pool.transcriber_for(&medium).is_none());
+        assert!(model_pool.transcriber_for(&small_en).is_some());
+        assert_eq!(model_pool.resolve_language("sv", &config, &deps)?, medium);
+
+        let loaded = transcriber_factory
+            .loaded_specs()
+            .into_iter()
+            .map(|(source, _)| source)
+            .collect::<Vec<_>>();
+        assert_eq!(loaded, vec![small_en, medium.clone(), medium]);
+        Ok(())
+    }
+
+    fn unloaded_models(events: &mpsc::Receiver<ipc::DaemonEvent>) -> Vec<(String, String)> {
+        events
+            .try_iter()
+            .filter_map(|event| match event.event {
+                ipc::DaemonEventType::ModelUnloaded {
+                    model_language,
+                    reason,
+                    ..
+                } => Some((model_language, reason)),
+                _ => None,
+            })
+            .collect()
     }
 
     #[test]
-    fn model_pool_switches_active_language_for_transcription() -> Result<(), AppError> {
-        let (sender, receiver) = control_channel();
-        let control_sender = sender.clone();
-        let shutdown = Arc::new(AtomicBool::new(false));
-        let mut output = TestOutput::default();
-        let transcriber_factory = TestTranscriberFactory::new(vec!["bonjour".to_string()]);
+    fn model_pool_unloads_least_recently_used_model_over_memory_budget() -> Result<(), AppError> {
+        let transcriber_factory = TestTranscriberFactory::new(Vec::new()).with_model_bytes(1 << 20);
         let deps = DaemonDeps {
-            audio: Box::new(TestAudioBackend::new(
-                vec!["Mic".to_string()],
-                vec![vec![0.2; 160]],
-            )),
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
             transcriber_factory: Box::new(transcriber_factory.clone()),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Small,
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 1,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "en".to_string(),
             model_variants: ModelVariants::Both,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
         };
+        let (event_sender, events) = mpsc::channel();
+        let small_en = ModelSource::Published(ModelSpec::new(ModelSize::Small, ModelLanguage::En));
+        let small = ModelSource::Published(ModelSpec::new(ModelSize::Small, ModelLanguage::Auto));
+
+        let mut model_pool = ModelPool::preload(&config, &deps, Some(&event_sender))?;
+        assert!(model_pool.transcriber_for(&small_en).is_some());
+        assert!(model_pool.transcriber_for(&small).is_none());
+
+        assert!(model_pool.ensure_loaded(&small, "fr", &config, &deps)?);
+        assert!(model_pool.transcriber_for(&small_en).is_none());
+        assert!(!model_pool.ensure_loaded(&small, "fr", &config, &deps)?);
+        assert_eq!(transcriber_factory.load_count(), 4);
+        assert_eq!(
+            unloaded_models(&events),
+            vec![
+                ("en".to_string(), "memory_budget".to_string()),
+                ("auto".to_string(), "memory_budget".to_string()),
+                ("en".to_string(), "memory_budget".to_string()),
+            ]
+        );
+        Ok(())
+    }
 
-        let shutdown_trigger = Arc::clone(&shutdown);
-        let control_thread = thread::spawn(move || {
-            let _ = control_sender.send(control_message(ControlEvent::SetLanguage {
-                language: "fr".to_string(),
-            }));
-            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
-            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
-            thread::sleep(Duration::from_millis(50));
-            shutdown_trigger.store(true, Ordering::Relaxed);
-        });
+    #[test]
+    fn model_pool_unloads_idle_models_and_reloads_on_use() -> Result<(), AppError> {
+        let transcriber_factory = TestTranscriberFactory::new(Vec::new());
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 60_000,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::En,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+        let (event_sender, events) = mpsc::channel();
+        let small_en = ModelSource::Published(ModelSpec::new(ModelSize::Small, ModelLanguage::En));
 
-        let result = run_daemon_loop(&config, &deps, &mut output, receiver, &shutdown, None);
-        control_thread.join().expect("control thread failed");
-        result?;
+        let base_en = ModelSource::Published(ModelSpec::new(ModelSize::Base, ModelLanguage::En));
 
-        assert_eq!(transcriber_factory.load_count(), 2);
+        let mut model_pool = ModelPool::preload(&config, &deps, Some(&event_sender))?;
+        assert!(model_pool.ensure_loaded(&base_en, "en", &config, &deps)?);
+        model_pool.unload_idle(Instant::now(), &small_en);
+        assert!(model_pool.transcriber_for(&base_en).is_some());
+
+        model_pool.unload_idle(Instant::now() + Duration::from_secs(61), &small_en);
+        assert!(model_pool.transcriber_for(&base_en).is_none());
         assert_eq!(
-            transcriber_factory.transcribed_languages(),
-            vec![Some("fr".to_string())]
+            unloaded_models(&events),
+            vec![("en".to_string(), "idle".to_string())]
         );
+
+        assert!(model_pool.ensure_loaded(&base_en, "en", &config, &deps)?);
+        assert!(model_pool.transcriber_for(&base_en).is_some());
+        assert_eq!(transcriber_factory.load_count(), 3);
         Ok(())
     }
 
     #[test]
-    fn model_pool_loads_new_language_on_set_language() -> Result<(), AppError> {
+    fn daemon_loop_reports_unverifiable_models_through_output() -> Result<(), AppError> {
+        let (_sender, receiver) = control_channel();
+        let shutdown = AtomicBool::new(true);
+        let mut output = TestOutput::default();
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
+            transcriber_factory: Box::new(TestTranscriberFactory::new(Vec::new())),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: true,
+            language: "en".to_string(),
+            model_variants: ModelVariants::En,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        run_daemon_loop(&config, &deps, &mut output, receiver, &shutdown, None)?;
+
+        let warned = output
+            .stderr_lines()
+            .iter()
+            .any(|line| line.contains("no checksum known for ggml-small.en.bin"));
+        assert_eq!(
+            warned,
+            model::expected_checksum("ggml-small.en.bin").is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_keeps_active_model_past_idle_timeout() -> Result<(), AppError> {
         let (sender, receiver) = control_channel();
         let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
         let shutdown = Arc::new(AtomicBool::new(false));
         let mut output = TestOutput::default();
-        let transcriber_factory = TestTranscriberFactory::new(vec!["hej".to_string()]);
+        let transcriber_factory = TestTranscriberFactory::new(vec!["hello".to_string()]);
         let deps = DaemonDeps {
             audio: Box::new(TestAudioBackend::new(
                 vec!["Mic".to_string()],
-                vec![vec![0.2; 160]],
+                vec![vec![0.2; 1_600]; 4],
             )),
             transcriber_factory: Box::new(transcriber_factory.clone()),
         };
         let config = DaemonConfig {
-            model_size: ModelSize::Small,
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 20,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
             download_model: false,
+            verify_model_checksum: false,
             language: "en".to_string(),
-            model_variants: ModelVariants::Both,
+            model_variants: ModelVariants::En,
             device: None,
             audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
             sample_rate: 16_000,
             format: OutputFormat::Plain,
             mode: OutputMode::Stdout,
             vad: VadMode::Off,
             vad_silence_ms: 800,
             vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
             debug_audio: false,
             debug_vad: false,
             dump_audio: false,
@@ -1844,24 +3615,1828 @@ mod tests {
 
         let shutdown_trigger = Arc::clone(&shutdown);
         let control_thread = thread::spawn(move || {
-            let _ = control_sender.send(control_message(ControlEvent::SetLanguage {
-                language: "sv".to_string(),
-            }));
+            thread::sleep(Duration::from_millis(100));
             let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
             let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
             thread::sleep(Duration::from_millis(50));
             shutdown_trigger.store(true, Ordering::Relaxed);
         });
 
-        let result = run_daemon_loop(&config, &deps, &mut output, receiver, &shutdown, None);
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
         control_thread.join().expect("control thread failed");
         result?;
 
-        assert_eq!(transcriber_factory.load_count(), 2);
+        let events = event_receiver
+            .try_iter()
+            .map(|event| event.event)
+            .collect::<Vec<_>>();
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, ipc::DaemonEventType::ModelUnloaded { .. })));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ipc::DaemonEventType::TranscriptFinal { text, .. } if text == "hello"
+        )));
+        assert_eq!(transcriber_factory.load_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn auto_model_size_resolves_from_benchmark() -> Result<(), AppError> {
+        let transcriber_factory = TestTranscriberFactory::new(Vec::new());
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: "auto-q5_0".parse::<ModelRef>().expect("model"),
+            models: HashMap::new(),
+            languages: HashMap::from([
+                ("sv".to_string(), ModelSize::Auto.into()),
+                ("de".to_string(), ModelSize::Base.into()),
+            ]),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: true,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Multilingual,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+        let mut output = TestOutput::default();
+
+        let resolved = resolve_auto_model(&config, &deps, &mut output)?.expect("auto resolved");
+
+        let ModelRef::Builtin(model) = resolved.model_size else {
+            panic!("expected a built-in model");
+        };
+        assert_ne!(model.size, ModelSize::Auto);
+        assert_eq!(model.quantization, Some(model::Quantization::Q5_0));
         assert_eq!(
-            transcriber_factory.transcribed_languages(),
-            vec![Some("sv".to_string())]
+            resolved.languages["sv"],
+            ModelRef::Builtin(model.size.into())
+        );
+        assert_eq!(resolved.languages["de"], ModelSize::Base.into());
+        assert_eq!(
+            transcriber_factory.loaded_specs()[0].0,
+            ModelSource::Published(ModelSpec::new(ModelSize::Tiny, ModelLanguage::Auto))
         );
+        assert!(output.stdout_lines()[0].contains("measured real-time factor"));
+
+        let fixed = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            languages: HashMap::new(),
+            ..config
+        };
+        assert!(resolve_auto_model(&fixed, &deps, &mut output)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn auto_model_size_detects_the_gpu_backend_without_loading_a_model() -> Result<(), AppError> {
+        let transcriber_factory = TestTranscriberFactory::new(Vec::new()).with_backend("Vulkan0");
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Auto.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Multilingual,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+        let mut output = TestOutput::default();
+
+        resolve_auto_model(&config, &deps, &mut output)?.expect("auto resolved");
+
+        // Without the benchmark nothing is loaded, so a missing tiny model cannot fail it.
+        assert_eq!(transcriber_factory.load_count(), 0);
+        assert!(output.stdout_lines()[0].contains("GPU Vulkan0"));
+        assert!(output.stdout_lines()[0].contains("estimated real-time factor"));
+
+        let cpu_only = DaemonConfig {
+            gpu: GpuSelection::Off,
+            ..config
+        };
+        resolve_auto_model(&cpu_only, &deps, &mut output)?.expect("auto resolved");
+        assert!(output.stdout_lines()[1].contains("no GPU reported"));
+        Ok(())
+    }
+
+    #[test]
+    fn model_pool_allows_english_with_multilingual_variant_only() -> Result<(), AppError> {
+        let transcriber_factory = TestTranscriberFactory::new(Vec::new());
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Multilingual,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let mut model_pool = ModelPool::preload(&config, &deps, None)?;
+        let resolved = model_pool.resolve_language("en", &config, &deps)?;
+        assert_eq!(
+            resolved,
+            ModelSource::Published(ModelSpec::new(ModelSize::Small, ModelLanguage::Auto))
+        );
+        assert_eq!(transcriber_factory.load_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn model_pool_rejects_non_english_with_en_only_variant() {
+        let transcriber_factory = TestTranscriberFactory::new(Vec::new());
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(vec!["Mic".to_string()], Vec::new())),
+            transcriber_factory: Box::new(transcriber_factory),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_variants: ModelVariants::En,
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "sv".to_string(),
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let err = ModelPool::preload(&config, &deps, None)
+            .err()
+            .expect("expected config error");
+        assert!(err
+            .to_string()
+            .contains("no compatible model variant configured"));
+    }
+
+    #[test]
+    fn model_pool_switches_active_language_for_transcription() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory = TestTranscriberFactory::new(vec!["bonjour".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.2; 160]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::SetLanguage {
+                language: "fr".to_string(),
+            }));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(&config, &deps, &mut output, receiver, &shutdown, None);
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert_eq!(transcriber_factory.load_count(), 2);
+        assert_eq!(
+            transcriber_factory.transcribed_languages(),
+            vec![Some("fr".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn model_pool_loads_new_language_on_set_language() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory = TestTranscriberFactory::new(vec!["hej".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.2; 160]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 800,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 250,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::SetLanguage {
+                language: "sv".to_string(),
+            }));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(&config, &deps, &mut output, receiver, &shutdown, None);
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert_eq!(transcriber_factory.load_count(), 2);
+        assert_eq!(
+            transcriber_factory.transcribed_languages(),
+            vec![Some("sv".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_auto_stops_after_trailing_silence() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory = TestTranscriberFactory::new(vec!["hello".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.2; 1_600], vec![0.0; 3_200], vec![0.0; 3_200]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: true,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(150));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert!(output
+            .stdout_lines()
+            .iter()
+            .any(|line| line.contains("Transcript 1: hello")));
+        assert_eq!(transcriber_factory.transcribed_languages().len(), 1);
+        let stop_reasons = event_receiver
+            .try_iter()
+            .filter_map(|event| match event.event {
+                ipc::DaemonEventType::RecordingStopped { reason, .. } => Some(reason),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(stop_reasons, vec![ipc::StopReason::SilenceTimeout]);
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_skips_transcription_without_speech() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory =
+            TestTranscriberFactory::new(vec!["Thanks for watching!".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.001; 4_800]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert!(transcriber_factory.transcribed_languages().is_empty());
+        assert!(!output
+            .stdout_lines()
+            .iter()
+            .any(|line| line.starts_with("Transcript")));
+        let events = event_receiver.try_iter().collect::<Vec<_>>();
+        assert!(events.iter().any(|event| matches!(
+            event.event,
+            ipc::DaemonEventType::NoSpeech {
+                duration_ms: 300,
+                ..
+            }
+        )));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event.event, ipc::DaemonEventType::TranscriptFinal { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn input_gain_lifts_quiet_speech_above_the_vad_threshold() -> Result<(), AppError> {
+        // A quiet mic: a tone with an RMS of about 0.006, under the 0.015 threshold.
+        let quiet_speech = (0..4_800)
+            .map(|index| {
+                0.008 * (index as f32 * 2.0 * std::f32::consts::PI * 220.0 / 16_000.0).sin()
+            })
+            .collect::<Vec<f32>>();
+        let run = |input_gain_db: f32| -> Result<Vec<ipc::DaemonEventType>, AppError> {
+            let (sender, receiver) = control_channel();
+            let control_sender = sender.clone();
+            let (event_sender, event_receiver) = mpsc::channel();
+            let shutdown = Arc::new(AtomicBool::new(false));
+            let mut output = TestOutput::default();
+            let deps = DaemonDeps {
+                audio: Box::new(TestAudioBackend::new(
+                    vec!["Mic".to_string()],
+                    vec![quiet_speech.clone()],
+                )),
+                transcriber_factory: Box::new(TestTranscriberFactory::new(vec![
+                    "hello".to_string()
+                ])),
+            };
+            let config = DaemonConfig {
+                model_size: ModelSize::Small.into(),
+                models: HashMap::new(),
+                languages: HashMap::new(),
+                model_idle_unload_ms: 0,
+                model_memory_budget_mb: 0,
+                model_auto_benchmark: false,
+                model_auto_target_rtf: 0.5,
+                gpu: GpuSelection::Auto,
+                flash_attn: false,
+                download_model: false,
+                verify_model_checksum: false,
+                language: "en".to_string(),
+                model_variants: ModelVariants::Both,
+                device: None,
+                audio_host: AudioHost::Default,
+                device_fallback: DeviceFallback::Default,
+                channel: ChannelSelection::Average,
+                sample_rate: 16_000,
+                format: OutputFormat::Plain,
+                mode: OutputMode::Stdout,
+                vad: VadMode::On,
+                vad_silence_ms: 300,
+                vad_threshold: 0.015,
+                device_thresholds: HashMap::new(),
+                vad_chunk_ms: 100,
+                vad_auto_stop: false,
+                vad_backend: VadBackend::Energy,
+                vad_model: None,
+                vad_probability: 0.5,
+                vad_auto_calibrate: false,
+                vad_calibration_ms: 500,
+                audio_level_interval_ms: 0,
+                warm_capture: false,
+                pre_roll_ms: 300,
+                resample_quality: ResampleQuality::Sinc,
+                input_gain_db,
+                normalize: NormalizeMode::Off,
+                dc_filter: false,
+                denoise: false,
+                debug_audio: false,
+                debug_vad: false,
+                dump_audio: false,
+            };
+
+            let shutdown_trigger = Arc::clone(&shutdown);
+            let control_thread = thread::spawn(move || {
+                let _ =
+                    control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+                thread::sleep(Duration::from_millis(50));
+                let _ =
+                    control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+                thread::sleep(Duration::from_millis(50));
+                shutdown_trigger.store(true, Ordering::Relaxed);
+            });
+            let result = run_daemon_loop(
+                &config,
+                &deps,
+                &mut output,
+                receiver,
+                &shutdown,
+                Some(&event_sender),
+            );
+            control_thread.join().expect("control thread failed");
+            result?;
+            Ok(event_receiver.try_iter().map(|event| event.event).collect())
+        };
+
+        let unamplified = run(0.0)?;
+        assert!(unamplified
+            .iter()
+            .any(|event| matches!(event, ipc::DaemonEventType::NoSpeech { .. })));
+        assert!(!unamplified
+            .iter()
+            .any(|event| matches!(event, ipc::DaemonEventType::TranscriptFinal { .. })));
+
+        let amplified = run(20.0)?;
+        assert!(amplified.iter().any(|event| matches!(
+            event,
+            ipc::DaemonEventType::TranscriptFinal { text, .. } if text == "hello"
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn denoise_lets_auto_stop_fire_over_steady_fan_noise() -> Result<(), AppError> {
+        // Broadband noise with an RMS of about 0.03, twice the VAD threshold,
+        // around half a second of speech.
+        let mut seed = 0x2545_f491u32;
+        let mut noise = |len: usize| {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    ((seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5) * 0.1
+                })
+                .collect::<Vec<f32>>()
+        };
+        let mut chunks = (0..5).map(|_| noise(1_600)).collect::<Vec<_>>();
+        for _ in 0..5 {
+            let mut speech = noise(1_600);
+            for (index, sample) in speech.iter_mut().enumerate() {
+                *sample +=
+                    0.3 * (index as f32 * 2.0 * std::f32::consts::PI * 440.0 / 16_000.0).sin();
+            }
+            chunks.push(speech);
+        }
+        chunks.extend((0..15).map(|_| noise(1_600)));
+
+        let run = |denoise: bool| -> Result<Vec<ipc::DaemonEventType>, AppError> {
+            let (sender, receiver) = control_channel();
+            let control_sender = sender.clone();
+            let (event_sender, event_receiver) = mpsc::channel();
+            let shutdown = Arc::new(AtomicBool::new(false));
+            let mut output = TestOutput::default();
+            let deps = DaemonDeps {
+                audio: Box::new(TestAudioBackend::new(
+                    vec!["Mic".to_string()],
+                    chunks.clone(),
+                )),
+                transcriber_factory: Box::new(TestTranscriberFactory::new(vec![
+                    "hello".to_string()
+                ])),
+            };
+            let config = DaemonConfig {
+                model_size: ModelSize::Small.into(),
+                models: HashMap::new(),
+                languages: HashMap::new(),
+                model_idle_unload_ms: 0,
+                model_memory_budget_mb: 0,
+                model_auto_benchmark: false,
+                model_auto_target_rtf: 0.5,
+                gpu: GpuSelection::Auto,
+                flash_attn: false,
+                download_model: false,
+                verify_model_checksum: false,
+                language: "en".to_string(),
+                model_variants: ModelVariants::Both,
+                device: None,
+                audio_host: AudioHost::Default,
+                device_fallback: DeviceFallback::Default,
+                channel: ChannelSelection::Average,
+                sample_rate: 16_000,
+                format: OutputFormat::Plain,
+                mode: OutputMode::Stdout,
+                vad: VadMode::On,
+                vad_silence_ms: 300,
+                vad_threshold: 0.015,
+                device_thresholds: HashMap::new(),
+                vad_chunk_ms: 100,
+                vad_auto_stop: true,
+                vad_backend: VadBackend::Energy,
+                vad_model: None,
+                vad_probability: 0.5,
+                vad_auto_calibrate: false,
+                vad_calibration_ms: 500,
+                audio_level_interval_ms: 100,
+                warm_capture: false,
+                pre_roll_ms: 300,
+                resample_quality: ResampleQuality::Sinc,
+                input_gain_db: 0.0,
+                normalize: NormalizeMode::Off,
+                dc_filter: false,
+                denoise,
+                debug_audio: false,
+                debug_vad: false,
+                dump_audio: false,
+            };
+
+            let shutdown_trigger = Arc::clone(&shutdown);
+            let control_thread = thread::spawn(move || {
+                let _ =
+                    control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+                thread::sleep(Duration::from_millis(1_000));
+                let _ =
+                    control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+                thread::sleep(Duration::from_millis(50));
+                shutdown_trigger.store(true, Ordering::Relaxed);
+            });
+            let result = run_daemon_loop(
+                &config,
+                &deps,
+                &mut output,
+                receiver,
+                &shutdown,
+                Some(&event_sender),
+            );
+            control_thread.join().expect("control thread failed");
+            result?;
+            Ok(event_receiver.try_iter().map(|event| event.event).collect())
+        };
+        let stop_reasons = |events: &[ipc::DaemonEventType]| {
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    ipc::DaemonEventType::RecordingStopped { reason, .. } => Some(*reason),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let level_speech = |events: &[ipc::DaemonEventType]| {
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    ipc::DaemonEventType::AudioLevel { speech, .. } => Some(*speech),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let raw = run(false)?;
+        assert_eq!(stop_reasons(&raw), vec![ipc::StopReason::Toggle]);
+        assert!(level_speech(&raw).iter().all(|speech| *speech));
+
+        let denoised = run(true)?;
+        // The second toggle starts a new recording that ends at shutdown.
+        assert_eq!(
+            stop_reasons(&denoised).first(),
+            Some(&ipc::StopReason::SilenceTimeout)
+        );
+        assert!(level_speech(&denoised).contains(&false));
+        assert!(denoised.iter().any(|event| matches!(
+            event,
+            ipc::DaemonEventType::TranscriptFinal { text, .. } if text == "hello"
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_auto_calibration_ignores_steady_background_noise() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory = TestTranscriberFactory::new(vec!["(fan noise)".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.03; 8_000], vec![0.03; 1_600]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: true,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: true,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(80));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert!(transcriber_factory.transcribed_languages().is_empty());
+        assert!(output
+            .stderr_lines()
+            .iter()
+            .any(|line| line == "vad noise floor 0.0300; threshold 0.0900"));
+        let events = event_receiver.try_iter().collect::<Vec<_>>();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.event, ipc::DaemonEventType::NoSpeech { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_emits_audio_level_events_while_recording() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.5; 1_600], vec![0.0; 1_600]],
+            )),
+            transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 100,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(80));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        let levels = event_receiver
+            .try_iter()
+            .filter_map(|event| match event.event {
+                ipc::DaemonEventType::AudioLevel {
+                    rms_dbfs,
+                    speech,
+                    clipping,
+                    ..
+                } => Some((rms_dbfs, speech, clipping)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(levels.len(), 2);
+        assert!(levels[0].1);
+        assert!(!levels[0].2);
+        assert!((levels[0].0 + 6.02).abs() < 0.01);
+        assert!(!levels[1].1);
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_prepends_pre_roll_from_warm_capture() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory = TestTranscriberFactory::new(vec!["hello".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.0; 3_200], vec![0.2; 1_600]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: true,
+            pre_roll_ms: 100,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            // Let the idle loop fill the pre-roll before toggling on.
+            thread::sleep(Duration::from_millis(150));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert_eq!(transcriber_factory.transcribed_languages().len(), 1);
+        let events = event_receiver.try_iter().collect::<Vec<_>>();
+        assert!(events.iter().any(|event| matches!(
+            event.event,
+            ipc::DaemonEventType::TranscriptFinal {
+                duration_ms: 100,
+                ..
+            }
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_reports_clipping_after_input_gain() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let transcriber_factory = TestTranscriberFactory::new(vec!["hello".to_string()]);
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.5; 1_600]],
+            )),
+            transcriber_factory: Box::new(transcriber_factory.clone()),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 12.0,
+            normalize: NormalizeMode::Peak,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        assert_eq!(transcriber_factory.transcribed_languages().len(), 1);
+        let clipped = event_receiver
+            .try_iter()
+            .find_map(|event| match event.event {
+                ipc::DaemonEventType::ClippingDetected {
+                    clipped_samples,
+                    gain_db,
+                    ..
+                } => Some((clipped_samples, gain_db)),
+                _ => None,
+            })
+            .expect("clipping_detected event");
+        assert!(clipped.0 > 0);
+        assert_eq!(clipped.1, 12.0);
+        assert!(output
+            .stderr_lines()
+            .iter()
+            .any(|line| line.contains("samples clipped")));
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_reports_capture_overflow() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let audio = TestAudioBackend::new(vec!["Mic".to_string()], vec![vec![0.2; 1_600]]);
+        audio.overflow().store(800, Ordering::SeqCst);
+        let deps = DaemonDeps {
+            audio: Box::new(audio),
+            transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: None,
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(control_message(ControlEvent::Toggle { language: None }));
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        let overflows = event_receiver
+            .try_iter()
+            .filter_map(|event| match event.event {
+                ipc::DaemonEventType::AudioOverflow {
+                    device,
+                    dropped_samples,
+                    dropped_ms,
+                } => Some((device, dropped_samples, dropped_ms)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(overflows, vec![("default".to_string(), 800, 50)]);
+        assert!(output
+            .stdout_lines()
+            .contains(&"Selected input device: default".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_falls_back_and_restores_lost_device() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let audio = TestAudioBackend::new(vec!["Mic".to_string()], vec![vec![0.2; 1_600]; 200]);
+        let connection = audio.connection();
+        let deps = DaemonDeps {
+            audio: Box::new(audio),
+            transcriber_factory: Box::new(TestTranscriberFactory::new(vec![
+                "one".to_string(),
+                "two".to_string(),
+                "three".to_string(),
+            ])),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: Some("Mic".to_string()),
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let toggle = || control_message(ControlEvent::Toggle { language: None });
+            let _ = control_sender.send(toggle());
+            thread::sleep(Duration::from_millis(50));
+            connection.store(false, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(toggle());
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(toggle());
+            thread::sleep(Duration::from_millis(50));
+            connection.store(true, Ordering::SeqCst);
+            let _ = control_sender.send(toggle());
+            thread::sleep(Duration::from_millis(50));
+            let _ = control_sender.send(toggle());
+            thread::sleep(Duration::from_millis(50));
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        let events = event_receiver
+            .try_iter()
+            .map(|event| event.event)
+            .filter(|event| {
+                matches!(
+                    event,
+                    ipc::DaemonEventType::DeviceLost { .. }
+                        | ipc::DaemonEventType::DeviceRestored { .. }
+                        | ipc::DaemonEventType::RecordingStopped { .. }
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                ipc::DaemonEventType::DeviceLost {
+                    device: "Mic".to_string(),
+                    message: "device disconnected".to_string(),
+                },
+                ipc::DaemonEventType::RecordingStopped {
+                    language: "en".to_string(),
+                    reason: ipc::StopReason::DeviceLost,
+                },
+                ipc::DaemonEventType::RecordingStopped {
+                    language: "en".to_string(),
+                    reason: ipc::StopReason::Toggle,
+                },
+                ipc::DaemonEventType::DeviceRestored {
+                    device: "Mic".to_string(),
+                },
+                ipc::DaemonEventType::RecordingStopped {
+                    language: "en".to_string(),
+                    reason: ipc::StopReason::Toggle,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_switches_device_over_ipc() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string(), "USB Headset".to_string()],
+                vec![vec![0.2; 1_600]; 10],
+            )),
+            transcriber_factory: Box::new(
+                TestTranscriberFactory::new(vec!["hello".to_string()]).with_backend("Vulkan0"),
+            ),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: Some("Mic".to_string()),
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Off,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::Off,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let request = |event| {
+                let (response_sender, response_receiver) = mpsc::channel();
+                let _ = control_sender.send(ControlMessage {
+                    event,
+                    response: Some(response_sender),
+                });
+                response_receiver
+                    .recv_timeout(Duration::from_secs(1))
+                    .expect("control response")
+            };
+            let changed = request(ControlEvent::SetDevice {
+                device: "headset".to_string(),
+            });
+            let status = request(ControlEvent::Status);
+            let missing = request(ControlEvent::SetDevice {
+                device: "Webcam".to_string(),
+            });
+            shutdown_trigger.store(true, Ordering::Relaxed);
+            (changed, status, missing)
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        let (changed, status, missing) = control_thread.join().expect("control thread failed");
+        result?;
+
+        assert!(changed.ok);
+        assert_eq!(changed.device.as_deref(), Some("headset"));
+        assert_eq!(status.device.as_deref(), Some("headset"));
+        assert_eq!(status.backend.as_deref(), Some("Vulkan0"));
+        assert!(!missing.ok);
+        assert_eq!(missing.error.as_deref(), Some("device_not_found"));
+        let events = event_receiver
+            .try_iter()
+            .map(|event| event.event)
+            .collect::<Vec<_>>();
+        assert!(events.contains(&ipc::DaemonEventType::DeviceChanged {
+            device: "headset".to_string(),
+        }));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ipc::DaemonEventType::ModelLoaded { backend, .. } if backend.as_deref() == Some("Vulkan0")
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn daemon_loop_applies_calibrated_threshold_of_the_opened_device() -> Result<(), AppError> {
+        let (sender, receiver) = control_channel();
+        let control_sender = sender.clone();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let mut output = TestOutput::default();
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string(), "USB Headset".to_string()],
+                vec![vec![0.2; 1_600]; 20],
+            )),
+            transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: Some("Mic".to_string()),
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Off,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            // Stored under the resolved name while set-device only asks for "headset".
+            device_thresholds: HashMap::from([("usb headset".to_string(), 0.3)]),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let shutdown_trigger = Arc::clone(&shutdown);
+        let control_thread = thread::spawn(move || {
+            let request = |event| {
+                let (response_sender, response_receiver) = mpsc::channel();
+                let _ = control_sender.send(ControlMessage {
+                    event,
+                    response: Some(response_sender),
+                });
+                response_receiver
+                    .recv_timeout(Duration::from_secs(1))
+                    .expect("control response")
+            };
+            request(ControlEvent::Toggle { language: None });
+            thread::sleep(Duration::from_millis(60));
+            request(ControlEvent::Toggle { language: None });
+            request(ControlEvent::SetDevice {
+                device: "headset".to_string(),
+            });
+            request(ControlEvent::Toggle { language: None });
+            thread::sleep(Duration::from_millis(60));
+            request(ControlEvent::Toggle { language: None });
+            shutdown_trigger.store(true, Ordering::Relaxed);
+        });
+
+        let result = run_daemon_loop(
+            &config,
+            &deps,
+            &mut output,
+            receiver,
+            &shutdown,
+            Some(&event_sender),
+        );
+        control_thread.join().expect("control thread failed");
+        result?;
+
+        let outcomes = event_receiver
+            .try_iter()
+            .map(|event| event.event)
+            .filter(|event| {
+                matches!(
+                    event,
+                    ipc::DaemonEventType::TranscriptFinal { .. }
+                        | ipc::DaemonEventType::NoSpeech { .. }
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(
+            &outcomes[0],
+            ipc::DaemonEventType::TranscriptFinal { text, .. } if text == "hello"
+        ));
+        assert!(matches!(outcomes[1], ipc::DaemonEventType::NoSpeech { .. }));
+        assert!(output
+            .stdout_lines()
+            .iter()
+            .any(|line| line == "VAD threshold: 0.3000 (calibrated for usb headset)"));
+        Ok(())
+    }
+
+    #[test]
+    fn run_calibration_measures_noise_floor() -> Result<(), AppError> {
+        let deps = DaemonDeps {
+            audio: Box::new(TestAudioBackend::new(
+                vec!["Mic".to_string()],
+                vec![vec![0.005; 8_000], vec![0.005; 8_000]],
+            )),
+            transcriber_factory: Box::new(TestTranscriberFactory::new(Vec::new())),
+        };
+        let config = DaemonConfig {
+            model_size: ModelSize::Small.into(),
+            models: HashMap::new(),
+            languages: HashMap::new(),
+            model_idle_unload_ms: 0,
+            model_memory_budget_mb: 0,
+            model_auto_benchmark: false,
+            model_auto_target_rtf: 0.5,
+            gpu: GpuSelection::Auto,
+            flash_attn: false,
+            download_model: false,
+            verify_model_checksum: false,
+            language: "en".to_string(),
+            model_variants: ModelVariants::Both,
+            device: Some("Mic".to_string()),
+            audio_host: AudioHost::Default,
+            device_fallback: DeviceFallback::Default,
+            channel: ChannelSelection::Average,
+            sample_rate: 16_000,
+            format: OutputFormat::Plain,
+            mode: OutputMode::Stdout,
+            vad: VadMode::On,
+            vad_silence_ms: 300,
+            vad_threshold: 0.015,
+            device_thresholds: HashMap::new(),
+            vad_chunk_ms: 100,
+            vad_auto_stop: false,
+            vad_backend: VadBackend::Energy,
+            vad_model: None,
+            vad_probability: 0.5,
+            vad_auto_calibrate: false,
+            vad_calibration_ms: 500,
+            audio_level_interval_ms: 0,
+            warm_capture: false,
+            pre_roll_ms: 300,
+            resample_quality: ResampleQuality::Sinc,
+            input_gain_db: 0.0,
+            normalize: NormalizeMode::Off,
+            dc_filter: false,
+            denoise: false,
+            debug_audio: false,
+            debug_vad: false,
+            dump_audio: false,
+        };
+
+        let (device, calibration) = run_calibration(&config, &deps, 1_000)?;
+
+        assert_eq!(device.as_deref(), Some("Mic"));
+        assert!((calibration.noise_floor - 0.005).abs() < 1e-6);
+        assert!((calibration.threshold - 0.015).abs() < 1e-6);
         Ok(())
     }
 
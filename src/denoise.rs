@@ -0,0 +1,251 @@
+use std::f32::consts::PI;
+
+// Roughly 32 ms frames; rounded up to a power of two for the FFT.
+const FRAME_MS: u32 = 32;
+// Bins are attenuated unless they rise this far above the noise estimate.
+const OVER_SUBTRACTION: f32 = 2.0;
+// Gain floor (-20 dB) keeps residual noise from turning into musical tones.
+const GAIN_FLOOR: f32 = 0.1;
+// Per-frame release of the bin gains, so speech tails are not chopped.
+const GAIN_RELEASE: f32 = 0.5;
+// Per-frame tracking of the noise estimate: it moves 5% towards quieter
+// frames and grows at most 2% (about 10 dB/s at 16 kHz) towards louder ones,
+// so steady noise is learned while speech barely moves it.
+const NOISE_FALL: f32 = 0.05;
+const NOISE_RISE: f32 = 1.02;
+
+// Streaming spectral gate: frames the input as it arrives (50% overlap) and
+// attenuates every STFT bin that does not rise clearly above a running noise
+// spectrum, seeded from the first frame. Output lags the input by up to one
+// frame; `flush` emits the rest.
+pub struct SpectralGate {
+    frame_len: usize,
+    hop: usize,
+    window: Vec<f32>,
+    pending: Vec<f32>,
+    overlap: Vec<f32>,
+    noise: Option<Vec<f32>>,
+    gains: Vec<f32>,
+    skip: usize,
+    held: usize,
+    re: Vec<f32>,
+    im: Vec<f32>,
+}
+
+impl SpectralGate {
+    pub fn new(sample_rate: u32) -> Self {
+        let frame_len = ((sample_rate as usize * FRAME_MS as usize) / 1000)
+            .max(64)
+            .next_power_of_two();
+        let hop = frame_len / 2;
+        // sqrt-Hann analysis and synthesis windows sum to one at 50% overlap.
+        let window = (0..frame_len)
+            .map(|index| (0.5 - 0.5 * (2.0 * PI * index as f32 / frame_len as f32).cos()).sqrt())
+            .collect();
+        Self {
+            frame_len,
+            hop,
+            window,
+            pending: vec![0.0; hop],
+            overlap: vec![0.0; frame_len],
+            noise: None,
+            gains: vec![1.0; frame_len / 2 + 1],
+            skip: hop,
+            held: 0,
+            re: vec![0.0; frame_len],
+            im: vec![0.0; frame_len],
+        }
+    }
+
+    // Gates `input` and appends every sample that is complete to `output`.
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        self.pending.extend_from_slice(input);
+        self.held += input.len();
+        while self.pending.len() >= self.frame_len {
+            self.gate_frame();
+            self.emit_hop(output);
+            self.pending.drain(..self.hop);
+        }
+    }
+
+    // Emits the samples still held back and starts framing afresh; the noise
+    // estimate is kept.
+    pub fn flush(&mut self, output: &mut Vec<f32>) {
+        if self.held > 0 {
+            let zeros = vec![0.0; self.frame_len];
+            self.pending.extend_from_slice(&zeros);
+            while self.held > 0 && self.pending.len() >= self.frame_len {
+                self.gate_frame();
+                self.emit_hop(output);
+                self.pending.drain(..self.hop);
+            }
+        }
+        self.pending.clear();
+        self.pending.resize(self.hop, 0.0);
+        self.overlap.fill(0.0);
+        self.skip = self.hop;
+        self.held = 0;
+    }
+
+    // Forgets the noise estimate too, for a new input stream.
+    pub fn reset(&mut self) {
+        self.flush(&mut Vec::new());
+        self.noise = None;
+        self.gains.fill(1.0);
+    }
+
+    fn gate_frame(&mut self) {
+        let bins = self.gains.len();
+        for (index, (sample, weight)) in self.pending[..self.frame_len]
+            .iter()
+            .zip(&self.window)
+            .enumerate()
+        {
+            self.re[index] = sample * weight;
+            self.im[index] = 0.0;
+        }
+        fft(&mut self.re, &mut self.im, false);
+
+        let noise = self.noise.get_or_insert_with(|| {
+            (0..bins)
+                .map(|bin| self.re[bin].hypot(self.im[bin]))
+                .collect()
+        });
+        for (bin, noise) in noise.iter_mut().enumerate() {
+            let magnitude = self.re[bin].hypot(self.im[bin]);
+            *noise = if magnitude < *noise {
+                *noise + (magnitude - *noise) * NOISE_FALL
+            } else {
+                (*noise * NOISE_RISE).min(magnitude)
+            };
+            let gate = if magnitude > 0.0 {
+                (1.0 - OVER_SUBTRACTION * *noise / magnitude).max(GAIN_FLOOR)
+            } else {
+                GAIN_FLOOR
+            };
+            self.gains[bin] = gate.max(self.gains[bin] * GAIN_RELEASE);
+        }
+        for bin in 0..self.frame_len {
+            // Mirror the gain onto the negative frequencies to keep the signal real.
+            let gain = self.gains[bin.min(self.frame_len - bin)];
+            self.re[bin] *= gain;
+            self.im[bin] *= gain;
+        }
+        fft(&mut self.re, &mut self.im, true);
+        for (index, value) in self.re.iter().enumerate() {
+            self.overlap[index] += value * self.window[index];
+        }
+    }
+
+    // The first hop of the overlap buffer has received both of its frames.
+    fn emit_hop(&mut self, output: &mut Vec<f32>) {
+        for &value in &self.overlap[..self.hop] {
+            if self.skip > 0 {
+                self.skip -= 1;
+            } else if self.held > 0 {
+                self.held -= 1;
+                output.push(value);
+            }
+        }
+        self.overlap.copy_within(self.hop.., 0);
+        let tail = self.frame_len - self.hop;
+        self.overlap[tail..].fill(0.0);
+    }
+}
+
+// In-place iterative radix-2 FFT; `re.len()` must be a power of two.
+fn fft(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let len = re.len();
+    let mut target = 0;
+    for index in 1..len {
+        let mut bit = len >> 1;
+        while target & bit != 0 {
+            target ^= bit;
+            bit >>= 1;
+        }
+        target |= bit;
+        if index < target {
+            re.swap(index, target);
+            im.swap(index, target);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut size = 2;
+    while size <= len {
+        let angle = sign * 2.0 * PI / size as f32;
+        for start in (0..len).step_by(size) {
+            for offset in 0..size / 2 {
+                let (sin, cos) = (angle * offset as f32).sin_cos();
+                let even = start + offset;
+                let odd = even + size / 2;
+                let odd_re = re[odd] * cos - im[odd] * sin;
+                let odd_im = re[odd] * sin + im[odd] * cos;
+                re[odd] = re[even] - odd_re;
+                im[odd] = im[even] - odd_im;
+                re[even] += odd_re;
+                im[even] += odd_im;
+            }
+        }
+        size <<= 1;
+    }
+
+    if inverse {
+        let scale = 1.0 / len as f32;
+        for (re, im) in re.iter_mut().zip(im.iter_mut()) {
+            *re *= scale;
+            *im *= scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn fft_round_trip_restores_signal() {
+        let original: Vec<f32> = (0..64).map(|index| (index as f32 * 0.3).sin()).collect();
+        let mut re = original.clone();
+        let mut im = vec![0.0; 64];
+        fft(&mut re, &mut im, false);
+        fft(&mut re, &mut im, true);
+        for (restored, expected) in re.iter().zip(&original) {
+            assert!((restored - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn spectral_gate_attenuates_noise_and_keeps_tone() {
+        let mut seed = 0x2545_f491u32;
+        let mut samples: Vec<f32> = (0..32_000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                ((seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5) * 0.1
+            })
+            .collect();
+        for (index, sample) in samples[16_000..24_000].iter_mut().enumerate() {
+            *sample += 0.5 * (2.0 * PI * 440.0 * index as f32 / 16_000.0).sin();
+        }
+        let noise_before = rms(&samples[2_000..14_000]);
+        let tone_before = rms(&samples[17_000..23_000]);
+
+        // Feed it in uneven blocks, as drained from a capture.
+        let mut gate = SpectralGate::new(16_000);
+        let mut gated = Vec::new();
+        for block in samples.chunks(1_000) {
+            gate.process(block, &mut gated);
+        }
+        gate.flush(&mut gated);
+        let samples = gated;
+
+        assert_eq!(samples.len(), 32_000);
+        assert!(rms(&samples[2_000..14_000]) < noise_before * 0.5);
+        let tone_after = rms(&samples[17_000..23_000]);
+        assert!((tone_after / tone_before - 1.0).abs() < 0.1, "{tone_after}");
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Cursor, Read};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::audio::{AudioError, AudioErrorKind, ChannelMixer};
+use crate::daemon::{AudioBackend, CaptureSource};
+use crate::resample::Resampler;
+use crate::sound_server::F32Decoder;
+use crate::types::{ChannelSelection, ResampleQuality};
+
+pub const STDIN_DEVICE: &str = "-";
+
+const BATCH_FRAMES: usize = 4096;
+
+// Reads audio from a WAV file, a named pipe or stdin (`-`). WAV input may use
+// any rate and channel count; anything without a RIFF header is treated as raw
+// mono 32-bit float little-endian PCM at the daemon sample rate. Samples are
+// released at the daemon rate, so a file plays back like a live microphone.
+//
+// Each source keeps one reader for the backend's lifetime. It only advances
+// while a capture is open, so toggling resumes where the last recording
+// stopped instead of discarding or replaying input.
+pub struct FileBackend {
+    resample_quality: ResampleQuality,
+    channel: ChannelSelection,
+    inputs: Mutex<HashMap<String, Arc<SharedInput>>>,
+}
+
+impl FileBackend {
+    pub fn new(resample_quality: ResampleQuality, channel: ChannelSelection) -> Self {
+        Self {
+            resample_quality,
+            channel,
+            inputs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Reuses the running reader for `source`; one that hit the end or failed is
+    // replaced, so a pipe can take a new writer on the next toggle.
+    fn input(
+        &self,
+        source: &str,
+        path: Option<std::path::PathBuf>,
+        sample_rate: u32,
+    ) -> Arc<SharedInput> {
+        let mut inputs = self.inputs.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(input) = inputs.get(source) {
+            if !input.lock().stopped() {
+                return Arc::clone(input);
+            }
+        }
+        let input = Arc::new(SharedInput::default());
+        let reader = Arc::clone(&input);
+        let format = InputFormat {
+            sample_rate,
+            quality: self.resample_quality,
+            channel: self.channel,
+        };
+        thread::spawn(move || {
+            // Opening a FIFO blocks until a writer connects, so do it off the daemon loop.
+            let result = match path {
+                Some(path) => {
+                    File::open(&path).and_then(|file| stream_audio(file, format, &reader))
+                }
+                None => stream_audio(io::stdin(), format, &reader),
+            };
+            let mut state = reader.lock();
+            match result {
+                Ok(()) => state.ended = true,
+                Err(err) => state.error = Some(format!("audio input error: {err}")),
+            }
+        });
+        inputs.insert(source.to_string(), Arc::clone(&input));
+        input
+    }
+}
+
+impl Drop for FileBackend {
+    fn drop(&mut self) {
+        let inputs = self.inputs.get_mut().unwrap_or_else(|err| err.into_inner());
+        for input in inputs.values() {
+            input.lock().closed = true;
+            input.wake.notify_all();
+        }
+    }
+}
+
+impl AudioBackend for FileBackend {
+    fn list_input_devices(&self, _host: &cpal::Host) -> Result<Vec<String>, AudioError> {
+        Ok(vec![STDIN_DEVICE.to_string()])
+    }
+
+    fn device_exists(&self, _host: &cpal::Host, _devices: &[String], requested: &str) -> bool {
+        requested == STDIN_DEVICE || Path::new(requested).exists()
+    }
+
+    fn start_capture(
+        &self,
+        _host: &cpal::Host,
+        device_name: Option<&str>,
+        sample_rate: u32,
+    ) -> Result<Box<dyn CaptureSource>, AudioError> {
+        let source = device_name.ok_or_else(|| {
+            AudioError::new(
+                AudioErrorKind::DeviceUnavailable,
+                "audio_host = \"file\" requires device = <path> or \"-\" for stdin",
+            )
+        })?;
+        let path = (source != STDIN_DEVICE).then(|| Path::new(source).to_path_buf());
+        if let Some(path) = &path {
+            if !path.exists() {
+                return Err(AudioError::new(
+                    AudioErrorKind::DeviceNotFound,
+                    format!("input file not found: {}", path.display()),
+                ));
+            }
+        }
+        let input = self.input(source, path, sample_rate);
+        input.lock().forwarding = true;
+        input.wake.notify_all();
+        Ok(Box::new(FileCapture {
+            input,
+            source: source.to_string(),
+        }))
+    }
+}
+
+// State shared between a source's reader thread and the capture currently open on it.
+#[derive(Default)]
+struct SharedInput {
+    state: Mutex<InputState>,
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct InputState {
+    forwarding: bool,
+    closed: bool,
+    samples: Vec<f32>,
+    ended: bool,
+    error: Option<String>,
+}
+
+impl InputState {
+    fn stopped(&self) -> bool {
+        self.ended || self.error.is_some()
+    }
+}
+
+impl SharedInput {
+    fn lock(&self) -> MutexGuard<'_, InputState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    // Waits for an open capture, paces the batch and hands it over. Returns
+    // false once the backend is gone and the reader should stop.
+    fn forward(&self, samples: Vec<f32>, pacer: &mut Pacer) -> bool {
+        let mut state = self.lock();
+        if !state.forwarding {
+            state = self
+                .wake
+                .wait_while(state, |state| !state.forwarding && !state.closed)
+                .unwrap_or_else(|err| err.into_inner());
+            pacer.restart();
+        }
+        if state.closed {
+            return false;
+        }
+        drop(state);
+        pacer.wait(samples.len());
+        // A batch paced out after the capture closed is kept for the next one.
+        self.lock().samples.extend(samples);
+        true
+    }
+}
+
+struct FileCapture {
+    input: Arc<SharedInput>,
+    source: String,
+}
+
+impl Drop for FileCapture {
+    fn drop(&mut self) {
+        self.input.lock().forwarding = false;
+    }
+}
+
+impl CaptureSource for FileCapture {
+    fn drain(&mut self, output: &mut Vec<f32>) {
+        output.append(&mut self.input.lock().samples);
+    }
+
+    // A read or decode failure ends the input the same way an unplugged device does.
+    fn device_lost(&mut self) -> Option<String> {
+        self.input.lock().error.clone()
+    }
+
+    fn end_of_input(&mut self) -> bool {
+        self.input.lock().ended
+    }
+
+    fn details(&self) -> Vec<String> {
+        vec![format!("Reading audio from {}", self.source)]
+    }
+}
+
+// Holds each batch back until the previous ones would have finished playing.
+struct Pacer {
+    started: Instant,
+    sample_rate: u32,
+    sent: u64,
+}
+
+impl Pacer {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            started: Instant::now(),
+            sample_rate: sample_rate.max(1),
+            sent: 0,
+        }
+    }
+
+    // Playback resumes from now after a pause between recordings.
+    fn restart(&mut self) {
+        self.started = Instant::now();
+        self.sent = 0;
+    }
+
+    fn wait(&mut self, samples: usize) {
+        let due =
+            self.started + Duration::from_secs_f64(self.sent as f64 / f64::from(self.sample_rate));
+        if let Some(wait) = due.checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+        self.sent += samples as u64;
+    }
+}
+
+#[derive(Clone, Copy)]
+struct InputFormat {
+    sample_rate: u32,
+    quality: ResampleQuality,
+    channel: ChannelSelection,
+}
+
+fn stream_audio(mut reader: impl Read, format: InputFormat, input: &SharedInput) -> io::Result<()> {
+    let mut header = Vec::with_capacity(4);
+    (&mut reader).take(4).read_to_end(&mut header)?;
+    let reader = Cursor::new(header.clone()).chain(reader);
+    let mut pacer = Pacer::new(format.sample_rate);
+    if header == b"RIFF" {
+        stream_wav(reader, format, &mut pacer, input)
+    } else {
+        stream_raw(reader, &mut pacer, input)
+    }
+}
+
+fn stream_raw(mut reader: impl Read, pacer: &mut Pacer, input: &SharedInput) -> io::Result<()> {
+    let mut decoder = F32Decoder::default();
+    let mut bytes = [0u8; BATCH_FRAMES * 4];
+    loop {
+        let read = reader.read(&mut bytes)?;
+        if read == 0 || !input.forward(decoder.decode(&bytes[..read]), pacer) {
+            return Ok(());
+        }
+    }
+}
+
+fn stream_wav(
+    reader: impl Read,
+    format: InputFormat,
+    pacer: &mut Pacer,
+    input: &SharedInput,
+) -> io::Result<()> {
+    let mut wav = hound::WavReader::new(reader)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let spec = wav.spec();
+    let mut mixer = ChannelMixer::new(format.channel, spec.channels)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.message))?;
+    let channels = spec.channels.max(1) as usize;
+    let mut resampler = (spec.sample_rate != format.sample_rate)
+        .then(|| Resampler::new(spec.sample_rate, format.sample_rate, format.quality));
+
+    let samples: Box<dyn Iterator<Item = Result<f32, hound::Error>>> = match spec.sample_format {
+        hound::SampleFormat::Float => Box::new(wav.samples::<f32>()),
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+            Box::new(
+                wav.samples::<i32>()
+                    .map(move |sample| sample.map(|value| value as f32 * scale)),
+            )
+        }
+    };
+
+    let mut interleaved = Vec::with_capacity(BATCH_FRAMES * channels);
+    let mut mono = Vec::with_capacity(BATCH_FRAMES);
+    let mut output = Vec::new();
+    for sample in samples {
+        interleaved.push(sample.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?);
+        if interleaved.len() < BATCH_FRAMES * channels {
+            continue;
+        }
+        mixer.mix(&interleaved, &mut mono);
+        interleaved.clear();
+        if !send_batch(&mut mono, &mut output, resampler.as_mut(), pacer, input) {
+            return Ok(());
+        }
+    }
+    mixer.mix(&interleaved, &mut mono);
+    send_batch(&mut mono, &mut output, resampler.as_mut(), pacer, input);
+    Ok(())
+}
+
+fn send_batch(
+    mono: &mut Vec<f32>,
+    output: &mut Vec<f32>,
+    resampler: Option<&mut Resampler>,
+    pacer: &mut Pacer,
+    input: &SharedInput,
+) -> bool {
+    match resampler {
+        Some(resampler) => resampler.process(mono, output),
+        None => output.append(mono),
+    }
+    mono.clear();
+    input.forward(std::mem::take(output), pacer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::time::{Duration, Instant};
+
+    fn drain_until(capture: &mut dyn CaptureSource, expected: usize) -> Vec<f32> {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let mut samples = Vec::new();
+        while samples.len() < expected && Instant::now() < deadline {
+            capture.drain(&mut samples);
+            thread::sleep(Duration::from_millis(5));
+        }
+        samples
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("soundvibes-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn reads_stereo_wav_and_resamples_to_daemon_rate() {
+        let path = temp_path("stereo.wav");
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).expect("create wav");
+        for _ in 0..48_000 {
+            writer.write_sample(8_192i16).expect("write left");
+            writer.write_sample(8_192i16).expect("write right");
+        }
+        writer.finalize().expect("finalize wav");
+
+        let backend = FileBackend::new(ResampleQuality::Linear, ChannelSelection::Average);
+        let mut capture = backend
+            .start_capture(
+                &cpal::default_host(),
+                Some(path.to_str().expect("utf-8 path")),
+                16_000,
+            )
+            .expect("start capture");
+        let samples = drain_until(capture.as_mut(), 15_990);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(
+            (samples.len() as i64 - 16_000).abs() < 10,
+            "{}",
+            samples.len()
+        );
+        assert!((samples[100] - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn reads_raw_f32_input() {
+        let path = temp_path("raw.f32");
+        let bytes: Vec<u8> = [0.5f32, -0.5, 0.25]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        std::fs::write(&path, bytes).expect("write raw input");
+
+        let backend = FileBackend::new(ResampleQuality::Sinc, ChannelSelection::Average);
+        let mut capture = backend
+            .start_capture(
+                &cpal::default_host(),
+                Some(path.to_str().expect("utf-8 path")),
+                16_000,
+            )
+            .expect("start capture");
+        let samples = drain_until(capture.as_mut(), 3);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(samples, vec![0.5, -0.5, 0.25]);
+    }
+
+    #[test]
+    fn reopening_resumes_the_input_and_reports_its_end() {
+        let path = temp_path("resume.f32");
+        let expected: Vec<f32> = (0..12_000).map(|index| index as f32).collect();
+        let bytes: Vec<u8> = expected
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        std::fs::write(&path, bytes).expect("write raw input");
+        let source = path.to_str().expect("utf-8 path");
+
+        let backend = FileBackend::new(ResampleQuality::Sinc, ChannelSelection::Average);
+        let host = cpal::default_host();
+        let mut capture = backend
+            .start_capture(&host, Some(source), 16_000)
+            .expect("start capture");
+        let mut samples = drain_until(capture.as_mut(), 1);
+        assert!(!capture.end_of_input());
+        drop(capture);
+        // Nothing is read while no capture is open.
+        thread::sleep(Duration::from_millis(400));
+
+        let mut capture = backend
+            .start_capture(&host, Some(source), 16_000)
+            .expect("reopen capture");
+        samples.extend(drain_until(
+            capture.as_mut(),
+            expected.len() - samples.len(),
+        ));
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !capture.end_of_input() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        capture.drain(&mut samples);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(samples, expected);
+        assert!(capture.end_of_input());
+        assert_eq!(capture.device_lost(), None);
+    }
+
+    #[test]
+    fn truncated_wav_is_reported_as_device_lost() {
+        let path = temp_path("truncated.wav");
+        std::fs::write(&path, b"RIFF\x24\x00\x00\x00WAVEjunk").expect("write truncated wav");
+
+        let backend = FileBackend::new(ResampleQuality::Sinc, ChannelSelection::Average);
+        let mut capture = backend
+            .start_capture(
+                &cpal::default_host(),
+                Some(path.to_str().expect("utf-8 path")),
+                16_000,
+            )
+            .expect("start capture");
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let mut lost = None;
+        while lost.is_none() && Instant::now() < deadline {
+            lost = capture.device_lost();
+            thread::sleep(Duration::from_millis(5));
+        }
+        let _ = std::fs::remove_file(&path);
+
+        assert!(lost
+            .expect("reader error")
+            .starts_with("audio input error:"));
+    }
+
+    #[test]
+    fn missing_file_is_reported_as_device_not_found() {
+        let backend = FileBackend::new(ResampleQuality::Sinc, ChannelSelection::Average);
+        let err = match backend.start_capture(
+            &cpal::default_host(),
+            Some("/nonexistent/soundvibes.wav"),
+            16_000,
+        ) {
+            Ok(_) => panic!("expected missing file error"),
+            Err(err) => err,
+        };
+        assert_eq!(err.kind, AudioErrorKind::DeviceNotFound);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+use std::fs;
+use std::thread;
+
+use crate::model::{ModelLanguage, ModelSize, ModelSpec};
+
+pub const DEFAULT_TARGET_RTF: f32 = 0.5;
+// Sizes `model_size = "auto"` picks from, smallest first.
+const AUTO_LADDER: [ModelSize; 5] = [
+    ModelSize::Tiny,
+    ModelSize::Base,
+    ModelSize::Small,
+    ModelSize::Medium,
+    ModelSize::LargeV3,
+];
+// Rough single-thread CPU real-time factor of tiny; every other size scales by
+// its parameter count relative to tiny.
+const CPU_TINY_RTF_PER_THREAD: f32 = 0.25;
+// Assumed speed-up when whisper reports a GPU backend.
+const GPU_SPEEDUP: f32 = 10.0;
+const MIB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HardwareProfile {
+    pub available_memory_bytes: Option<u64>,
+    pub cpu_cores: usize,
+    pub gpu_backend: Option<String>,
+}
+
+impl HardwareProfile {
+    pub fn probe(gpu_backend: Option<String>) -> Self {
+        Self {
+            available_memory_bytes: fs::read_to_string("/proc/meminfo")
+                .ok()
+                .and_then(|meminfo| parse_available_memory(&meminfo)),
+            cpu_cores: thread::available_parallelism()
+                .map(|count| count.get())
+                .unwrap_or(1),
+            gpu_backend,
+        }
+    }
+
+    // Transcription uses half the cores (see `WhisperContext::transcribe`).
+    fn estimated_tiny_rtf(&self) -> f32 {
+        let threads = (self.cpu_cores / 2).max(1) as f32;
+        let cpu = CPU_TINY_RTF_PER_THREAD / threads;
+        if self.gpu_backend.is_some() {
+            cpu / GPU_SPEEDUP
+        } else {
+            cpu
+        }
+    }
+}
+
+impl fmt::Display for HardwareProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} cores", self.cpu_cores)?;
+        match self.available_memory_bytes {
+            Some(bytes) => write!(f, ", {} MiB available", bytes / MIB)?,
+            None => f.write_str(", available memory unknown")?,
+        }
+        match &self.gpu_backend {
+            Some(backend) => write!(f, ", GPU {backend}"),
+            None => f.write_str(", no GPU reported"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoModelChoice {
+    pub size: ModelSize,
+    pub estimated_rtf: f32,
+    pub measured: bool,
+}
+
+// Picks the largest size whose estimated real-time factor meets `target_rtf`
+// and whose memory footprint fits, falling back to tiny. `tiny_rtf` is a
+// measured benchmark result; without one the factor is estimated from the
+// core count and GPU backend. `variants` are the model languages kept loaded
+// (`model_variants`), each a separate file where the size has an `.en` model.
+pub fn choose_model_size(
+    profile: &HardwareProfile,
+    tiny_rtf: Option<f32>,
+    target_rtf: f32,
+    variants: &[ModelLanguage],
+) -> AutoModelChoice {
+    let base_rtf = tiny_rtf.unwrap_or_else(|| profile.estimated_tiny_rtf());
+    let tiny_cost = relative_cost(ModelSize::Tiny);
+    let mut choice = AutoModelChoice {
+        size: ModelSize::Tiny,
+        estimated_rtf: base_rtf,
+        measured: tiny_rtf.is_some(),
+    };
+    for size in AUTO_LADDER {
+        let estimated_rtf = base_rtf * relative_cost(size) / tiny_cost;
+        let fits = profile.available_memory_bytes.is_none_or(|available| {
+            required_memory_mib(size) * resident_models(size, variants) * MIB <= available
+        });
+        if estimated_rtf <= target_rtf && fits {
+            choice.size = size;
+            choice.estimated_rtf = estimated_rtf;
+        }
+    }
+    choice
+}
+
+fn resident_models(size: ModelSize, variants: &[ModelLanguage]) -> u64 {
+    let mut files = variants
+        .iter()
+        .map(|language| ModelSpec::for_model(size.into(), *language))
+        .collect::<Vec<_>>();
+    files.dedup();
+    files.len().max(1) as u64
+}
+
+// Parameter counts in millions.
+fn relative_cost(size: ModelSize) -> f32 {
+    match size {
+        ModelSize::Tiny => 39.0,
+        ModelSize::Base => 74.0,
+        ModelSize::Small => 244.0,
+        ModelSize::Medium => 769.0,
+        _ => 1550.0,
+    }
+}
+
+// Memory usage whisper.cpp documents for each size.
+fn required_memory_mib(size: ModelSize) -> u64 {
+    match size {
+        ModelSize::Tiny => 273,
+        ModelSize::Base => 388,
+        ModelSize::Small => 852,
+        ModelSize::Medium => 2_100,
+        _ => 3_900,
+    }
+}
+
+fn parse_available_memory(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let value = line.strip_prefix("MemAvailable:")?;
+        let kib = value
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(kib * 1024)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(cores: usize, memory_mib: u64, gpu: bool) -> HardwareProfile {
+        HardwareProfile {
+            available_memory_bytes: Some(memory_mib * MIB),
+            cpu_cores: cores,
+            gpu_backend: gpu.then(|| "Vulkan0".to_string()),
+        }
+    }
+
+    #[test]
+    fn auto_model_scales_with_cores_memory_and_gpu() {
+        let pick = |profile: &HardwareProfile| {
+            choose_model_size(profile, None, DEFAULT_TARGET_RTF, &[ModelLanguage::Auto]).size
+        };
+        assert_eq!(pick(&profile(2, 8_192, false)), ModelSize::Base);
+        assert_eq!(pick(&profile(8, 8_192, false)), ModelSize::Small);
+        assert_eq!(pick(&profile(32, 32_768, false)), ModelSize::Medium);
+        assert_eq!(pick(&profile(8, 16_384, true)), ModelSize::LargeV3);
+        assert_eq!(pick(&profile(8, 1_024, true)), ModelSize::Small);
+    }
+
+    #[test]
+    fn auto_model_uses_measured_benchmark() {
+        let slow = choose_model_size(
+            &profile(32, 32_768, true),
+            Some(0.4),
+            0.5,
+            &[ModelLanguage::Auto],
+        );
+        assert_eq!(slow.size, ModelSize::Tiny);
+        assert!(slow.measured);
+        let fast = choose_model_size(
+            &profile(2, 32_768, false),
+            Some(0.02),
+            0.5,
+            &[ModelLanguage::Auto],
+        );
+        assert_eq!(fast.size, ModelSize::Medium);
+    }
+
+    #[test]
+    fn auto_model_fits_both_variants_in_memory() {
+        let both = [ModelLanguage::En, ModelLanguage::Auto];
+        let fast = profile(32, 3_000, true);
+        let one = choose_model_size(&fast, Some(0.001), 0.5, &[ModelLanguage::En]);
+        assert_eq!(one.size, ModelSize::Medium);
+        // medium.en and medium need 4.2 GB together; small.en and small fit.
+        let two = choose_model_size(&fast, Some(0.001), 0.5, &both);
+        assert_eq!(two.size, ModelSize::Small);
+        // large-v3 serves English from its multilingual file, so it loads once.
+        let large = choose_model_size(&profile(32, 4_000, true), Some(0.001), 0.5, &both);
+        assert_eq!(large.size, ModelSize::LargeV3);
+    }
+
+    #[test]
+    fn parses_available_memory_from_meminfo() {
+        let meminfo =
+            "MemTotal:       16318412 kB\nMemFree:         1024 kB\nMemAvailable:    8159206 kB\n";
+        assert_eq!(parse_available_memory(meminfo), Some(8_159_206 * 1024));
+        assert_eq!(parse_available_memory("MemTotal: 1 kB\n"), None);
+    }
+}
@@ -1,6 +1,8 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::audio::InputDeviceInfo;
+
 pub const API_VERSION: &str = "1";
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +30,10 @@ pub enum ControlCommand {
         lang: Option<String>,
     },
     Status,
+    Devices,
+    SetDevice {
+        device: String,
+    },
     SetLanguage {
         lang: String,
     },
@@ -42,6 +48,10 @@ impl ControlCommand {
     pub fn set_language(lang: String) -> Self {
         Self::SetLanguage { lang }
     }
+
+    pub fn set_device(device: String) -> Self {
+        Self::SetDevice { device }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -57,6 +67,12 @@ pub struct ControlResponse {
     pub error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub devices: Option<Vec<InputDeviceInfo>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
 }
 
 impl ControlResponse {
@@ -68,9 +84,27 @@ impl ControlResponse {
             language,
             error: None,
             message: None,
+            device: None,
+            devices: None,
+            backend: None,
         }
     }
 
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = Some(device.into());
+        self
+    }
+
+    pub fn with_devices(mut self, devices: Vec<InputDeviceInfo>) -> Self {
+        self.devices = Some(devices);
+        self
+    }
+
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.backend = Some(backend.into());
+        self
+    }
+
     pub fn error(error: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             api_version: api_version_string(),
@@ -79,11 +113,14 @@ impl ControlResponse {
             language: None,
             error: Some(error.into()),
             message: Some(message.into()),
+            device: None,
+            devices: None,
+            backend: None,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DaemonEvent {
     #[serde(default = "api_version_string")]
     pub api_version: String,
@@ -102,7 +139,7 @@ impl DaemonEvent {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum DaemonEventType {
     DaemonReady,
@@ -111,6 +148,38 @@ pub enum DaemonEventType {
     },
     RecordingStopped {
         language: String,
+        #[serde(default)]
+        reason: StopReason,
+    },
+    NoSpeech {
+        language: String,
+        duration_ms: u64,
+    },
+    AudioLevel {
+        rms_dbfs: f32,
+        peak_dbfs: f32,
+        speech: bool,
+        clipping: bool,
+    },
+    AudioOverflow {
+        device: String,
+        dropped_samples: u64,
+        dropped_ms: u64,
+    },
+    DeviceLost {
+        device: String,
+        message: String,
+    },
+    DeviceRestored {
+        device: String,
+    },
+    DeviceChanged {
+        device: String,
+    },
+    ClippingDetected {
+        language: String,
+        clipped_samples: u64,
+        gain_db: f32,
     },
     TranscriptFinal {
         language: String,
@@ -122,12 +191,82 @@ pub enum DaemonEventType {
         language: String,
         model_size: String,
         model_language: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        backend: Option<String>,
+    },
+    ModelUnloaded {
+        model_size: String,
+        model_language: String,
+        reason: String,
+    },
+    ModelDownloadProgress {
+        model: String,
+        downloaded_bytes: u64,
+        total_bytes: Option<u64>,
     },
     Error {
         message: String,
     },
 }
 
+// Why a recording ended, reported with `recording_stopped`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    #[default]
+    Toggle,
+    SilenceTimeout,
+    DeviceLost,
+    EndOfInput,
+    Shutdown,
+}
+
+// Event types that are only sent to subscribers that ask for them, because
+// they are emitted many times per second.
+pub const OPT_IN_EVENTS: &[&str] = &["audio_level"];
+
+impl DaemonEventType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DaemonEventType::DaemonReady => "daemon_ready",
+            DaemonEventType::RecordingStarted { .. } => "recording_started",
+            DaemonEventType::RecordingStopped { .. } => "recording_stopped",
+            DaemonEventType::NoSpeech { .. } => "no_speech",
+            DaemonEventType::AudioLevel { .. } => "audio_level",
+            DaemonEventType::AudioOverflow { .. } => "audio_overflow",
+            DaemonEventType::DeviceLost { .. } => "device_lost",
+            DaemonEventType::DeviceRestored { .. } => "device_restored",
+            DaemonEventType::DeviceChanged { .. } => "device_changed",
+            DaemonEventType::ClippingDetected { .. } => "clipping_detected",
+            DaemonEventType::TranscriptFinal { .. } => "transcript_final",
+            DaemonEventType::ModelLoaded { .. } => "model_loaded",
+            DaemonEventType::ModelUnloaded { .. } => "model_unloaded",
+            DaemonEventType::ModelDownloadProgress { .. } => "model_download_progress",
+            DaemonEventType::Error { .. } => "error",
+        }
+    }
+}
+
+// Subscribers opt in by writing `subscribe <event> [<event>...]` lines to the
+// events socket.
+pub fn parse_subscribe_request(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("subscribe") {
+        return Err(format!("unknown events request: {}", line.trim()));
+    }
+    let events: Vec<String> = tokens.map(str::to_string).collect();
+    if events.is_empty() {
+        return Err("subscribe requires at least one event type".to_string());
+    }
+    if let Some(unknown) = events
+        .iter()
+        .find(|event| !OPT_IN_EVENTS.contains(&event.as_str()))
+    {
+        return Err(format!("unknown opt-in event: {unknown}"));
+    }
+    Ok(events)
+}
+
 pub fn to_json_line<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
     let mut json = serde_json::to_string(value)?;
     json.push('\n');
@@ -172,6 +311,12 @@ pub fn parse_control_request(command: &str) -> Result<ControlRequest, String> {
             }
             Ok(ControlRequest::new(ControlCommand::Status))
         }
+        "devices" => {
+            if let Some(token) = tokens.next() {
+                return Err(format!("unexpected token '{token}' for devices"));
+            }
+            Ok(ControlRequest::new(ControlCommand::Devices))
+        }
         "set-language" => {
             let mut lang = None;
             for token in tokens {
@@ -190,6 +335,20 @@ pub fn parse_control_request(command: &str) -> Result<ControlRequest, String> {
             let lang = lang.ok_or_else(|| "missing lang=<CODE>".to_string())?;
             Ok(ControlRequest::new(ControlCommand::set_language(lang)))
         }
+        // Device names often contain spaces, so the value is the rest of the line.
+        "set-device" => {
+            let rest = command.trim_start()[action.len()..].trim();
+            let device = rest
+                .strip_prefix("device=")
+                .ok_or_else(|| "missing device=<NAME>".to_string())?
+                .trim();
+            if device.is_empty() {
+                return Err("device value cannot be empty".to_string());
+            }
+            Ok(ControlRequest::new(ControlCommand::set_device(
+                device.to_string(),
+            )))
+        }
         "stop" => {
             if let Some(token) = tokens.next() {
                 return Err(format!("unexpected token '{token}' for stop"));
@@ -240,6 +399,42 @@ mod tests {
         assert_eq!(parsed, event);
     }
 
+    #[test]
+    fn recording_stopped_reason_serializes_as_snake_case() {
+        let event = DaemonEvent::new(
+            "2026-02-05T12:01:12Z",
+            DaemonEventType::RecordingStopped {
+                language: "en".to_string(),
+                reason: StopReason::SilenceTimeout,
+            },
+        );
+
+        let line = to_json_line(&event).expect("event should serialize");
+        assert!(line.contains("\"reason\":\"silence_timeout\""));
+        let legacy: DaemonEvent = from_json_line(
+            "{\"type\":\"recording_stopped\",\"timestamp\":\"t\",\"language\":\"en\"}",
+        )
+        .expect("event should parse");
+        assert_eq!(
+            legacy.event,
+            DaemonEventType::RecordingStopped {
+                language: "en".to_string(),
+                reason: StopReason::Toggle,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_subscribe_request_for_opt_in_events() {
+        assert_eq!(
+            parse_subscribe_request("subscribe audio_level\n"),
+            Ok(vec!["audio_level".to_string()])
+        );
+        assert!(parse_subscribe_request("subscribe").is_err());
+        assert!(parse_subscribe_request("subscribe transcript_final").is_err());
+        assert!(parse_subscribe_request("toggle").is_err());
+    }
+
     #[test]
     fn parses_control_response_json_line() {
         let line = "{\"api_version\":\"1\",\"ok\":true,\"state\":\"idle\",\"language\":\"en\"}\n";
@@ -249,4 +444,36 @@ mod tests {
         assert_eq!(response.state.as_deref(), Some("idle"));
         assert_eq!(response.language.as_deref(), Some("en"));
     }
+
+    #[test]
+    fn parses_set_device_with_spaces_in_name() {
+        assert_eq!(
+            parse_control_request("set-device device=Blue Yeti USB\n")
+                .map(|request| request.command),
+            Ok(ControlCommand::set_device("Blue Yeti USB".to_string()))
+        );
+        assert!(parse_control_request("set-device").is_err());
+        assert!(parse_control_request("set-device device=").is_err());
+        assert!(parse_control_request("set-device Mic").is_err());
+    }
+
+    #[test]
+    fn devices_response_round_trips_device_capabilities() {
+        assert_eq!(
+            parse_control_request("devices").map(|request| request.command),
+            Ok(ControlCommand::Devices)
+        );
+
+        let mut device = InputDeviceInfo::named("hw:CARD=Mic,DEV=0");
+        device.id = "alsa:hw:CARD=Mic,DEV=0".to_string();
+        device.sample_rates = vec![44_100, 48_000];
+        device.channels = vec![1, 2];
+        device.sample_formats = vec!["i16".to_string()];
+        let response = ControlResponse::ok(Some("idle".to_string()), Some("en".to_string()))
+            .with_devices(vec![device]);
+
+        let line = to_json_line(&response).expect("response should serialize");
+        assert!(line.contains("\"sample_rates\":[44100,48000]"));
+        assert_eq!(parse_control_response(&line).expect("parse"), response);
+    }
 }
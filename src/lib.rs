@@ -1,8 +1,15 @@
 pub mod audio;
+pub mod calibration;
 pub mod daemon;
+pub mod denoise;
 pub mod error;
+pub mod file_capture;
+pub mod hardware;
 pub mod ipc;
 pub mod model;
 pub mod output;
+pub mod preprocess;
+pub mod resample;
+pub mod sound_server;
 pub mod types;
 pub mod whisper;
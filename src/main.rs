@@ -1,25 +1,55 @@
 use clap::parser::ValueSource;
 use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::process;
 use sv::audio;
+use sv::calibration::{self, CalibrationStore};
 use sv::daemon;
 use sv::error::AppError;
-use sv::model::{ModelSize, ModelVariants};
-use sv::types::{AudioHost, OutputFormat, OutputMode, VadMode, VadSetting};
+use sv::hardware;
+use sv::model::{self, ModelId, ModelLanguage, ModelRef, ModelSource, ModelSpec, ModelVariants};
+use sv::preprocess;
+use sv::types::{
+    AudioHost, ChannelSelection, DeviceFallback, GpuSelection, NormalizeMode, OutputFormat,
+    OutputMode, ResampleQuality, VadBackend, VadMode, VadSetting,
+};
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "sv", version, about = "Offline speech-to-text CLI")]
 struct Cli {
-    #[arg(long, default_value = "small", value_name = "SIZE", global = true)]
-    model_size: ModelSize,
+    #[arg(long, default_value = "small", value_name = "MODEL", global = true)]
+    model_size: ModelRef,
 
     #[arg(long, value_name = "VARIANT", global = true)]
     model_variants: Option<ModelVariants>,
 
+    #[arg(long, default_value_t = 0, value_name = "MS", global = true)]
+    model_idle_unload_ms: u64,
+
+    #[arg(long, default_value_t = 0, value_name = "MB", global = true)]
+    model_memory_budget_mb: u64,
+
+    #[arg(long, default_value_t = false, global = true)]
+    model_auto_benchmark: bool,
+
+    #[arg(
+        long,
+        default_value_t = hardware::DEFAULT_TARGET_RTF,
+        value_name = "RTF",
+        global = true
+    )]
+    model_auto_target_rtf: f32,
+
+    #[arg(long, default_value = "auto", value_name = "GPU", global = true)]
+    gpu: GpuSelection,
+
+    #[arg(long, default_value_t = false, global = true)]
+    flash_attn: bool,
+
     #[arg(long, default_value = "en", value_name = "CODE", global = true)]
     language: String,
 
@@ -32,6 +62,12 @@ struct Cli {
     #[arg(long, value_name = "HOST", global = true)]
     audio_host: Option<AudioHost>,
 
+    #[arg(long, default_value = "default", value_name = "MODE", global = true)]
+    device_fallback: DeviceFallback,
+
+    #[arg(long, default_value = "average", value_name = "CHANNEL", global = true)]
+    channel: ChannelSelection,
+
     #[arg(long, default_value_t = 16_000, value_name = "HZ", global = true)]
     sample_rate: u32,
 
@@ -68,6 +104,74 @@ struct Cli {
     )]
     vad_chunk_ms: u64,
 
+    #[arg(long, default_value_t = false, global = true)]
+    vad_auto_stop: bool,
+
+    #[arg(long, default_value = "energy", value_name = "BACKEND", global = true)]
+    vad_backend: VadBackend,
+
+    #[arg(long, value_name = "PATH", global = true)]
+    vad_model: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value_t = audio::DEFAULT_VAD_PROBABILITY,
+        value_name = "PROB",
+        global = true
+    )]
+    vad_probability: f32,
+
+    #[arg(long, default_value_t = false, global = true)]
+    vad_auto_calibrate: bool,
+
+    #[arg(
+        long,
+        default_value_t = audio::DEFAULT_CALIBRATION_MS,
+        value_name = "MS",
+        global = true
+    )]
+    vad_calibration_ms: u64,
+
+    #[arg(
+        long,
+        default_value_t = audio::DEFAULT_AUDIO_LEVEL_INTERVAL_MS,
+        value_name = "MS",
+        global = true
+    )]
+    audio_level_interval_ms: u64,
+
+    #[arg(long, default_value_t = false, global = true)]
+    warm_capture: bool,
+
+    #[arg(
+        long,
+        default_value_t = audio::DEFAULT_PRE_ROLL_MS,
+        value_name = "MS",
+        global = true
+    )]
+    pre_roll_ms: u64,
+
+    #[arg(long, default_value = "sinc", value_name = "QUALITY", global = true)]
+    resample_quality: ResampleQuality,
+
+    #[arg(
+        long,
+        default_value_t = preprocess::DEFAULT_INPUT_GAIN_DB,
+        value_name = "DB",
+        allow_hyphen_values = true,
+        global = true
+    )]
+    input_gain_db: f32,
+
+    #[arg(long, default_value = "off", value_name = "MODE", global = true)]
+    normalize: NormalizeMode,
+
+    #[arg(long, default_value_t = false, global = true)]
+    dc_filter: bool,
+
+    #[arg(long, default_value_t = false, global = true)]
+    denoise: bool,
+
     #[arg(long, default_value_t = false, global = true)]
     debug_audio: bool,
 
@@ -83,6 +187,9 @@ struct Cli {
     #[arg(long, default_value_t = true, action = clap::ArgAction::Set, global = true)]
     download_model: bool,
 
+    #[arg(long, default_value_t = false, global = true)]
+    verify_model_checksum: bool,
+
     #[command(subcommand)]
     command: Option<CliCommand>,
 }
@@ -93,6 +200,35 @@ enum CliCommand {
         #[command(subcommand)]
         command: DaemonCommand,
     },
+    Calibrate {
+        #[arg(long = "duration-ms", default_value_t = 3000, value_name = "MS")]
+        duration_ms: u64,
+    },
+    Model {
+        #[command(subcommand)]
+        command: ModelCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+enum ModelCommand {
+    List,
+    Pull {
+        #[arg(value_name = "MODEL")]
+        model: ModelRef,
+    },
+    Rm {
+        #[arg(value_name = "MODEL")]
+        model: ModelRef,
+    },
+    Verify {
+        #[arg(value_name = "MODEL")]
+        model: Option<ModelRef>,
+    },
+    Path {
+        #[arg(value_name = "MODEL")]
+        model: Option<ModelRef>,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
@@ -105,6 +241,11 @@ enum DaemonCommand {
         #[arg(long = "lang", value_name = "CODE")]
         lang: String,
     },
+    #[command(name = "set-device")]
+    SetDevice {
+        #[arg(value_name = "NAME")]
+        device: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -114,7 +255,10 @@ enum CliMode {
     StatusDaemon,
     StopDaemon,
     SetLanguage { language: String },
+    SetDevice { device: String },
     ListDevices,
+    Calibrate { duration_ms: u64 },
+    Model { command: ModelCommand },
 }
 
 fn resolve_cli_mode(cli: &Cli) -> CliMode {
@@ -133,6 +277,15 @@ fn resolve_cli_mode(cli: &Cli) -> CliMode {
         }) => CliMode::SetLanguage {
             language: lang.clone(),
         },
+        Some(CliCommand::Daemon {
+            command: DaemonCommand::SetDevice { ref device },
+        }) => CliMode::SetDevice {
+            device: device.clone(),
+        },
+        Some(CliCommand::Calibrate { duration_ms }) => CliMode::Calibrate { duration_ms },
+        Some(CliCommand::Model { ref command }) => CliMode::Model {
+            command: command.clone(),
+        },
         None => {
             if cli.list_devices {
                 CliMode::ListDevices
@@ -145,20 +298,47 @@ fn resolve_cli_mode(cli: &Cli) -> CliMode {
 
 #[derive(Debug, Clone)]
 struct Config {
-    model_size: ModelSize,
+    model_size: ModelRef,
+    models: HashMap<String, PathBuf>,
+    languages: HashMap<String, ModelRef>,
     model_variants: ModelVariants,
+    model_idle_unload_ms: u64,
+    model_memory_budget_mb: u64,
+    model_auto_benchmark: bool,
+    model_auto_target_rtf: f32,
+    gpu: GpuSelection,
+    flash_attn: bool,
     download_model: bool,
+    verify_model_checksum: bool,
     language: String,
     toggle_language: Option<String>,
     device: Option<String>,
     audio_host: AudioHost,
+    device_fallback: DeviceFallback,
+    channel: ChannelSelection,
     sample_rate: u32,
     format: OutputFormat,
     mode: OutputMode,
     vad: VadMode,
     vad_silence_ms: u64,
     vad_threshold: f32,
+    device_thresholds: HashMap<String, f32>,
     vad_chunk_ms: u64,
+    vad_auto_stop: bool,
+    vad_backend: VadBackend,
+    vad_model: Option<PathBuf>,
+    vad_probability: f32,
+    vad_auto_calibrate: bool,
+    vad_calibration_ms: u64,
+    vad_threshold_explicit: bool,
+    audio_level_interval_ms: u64,
+    warm_capture: bool,
+    pre_roll_ms: u64,
+    resample_quality: ResampleQuality,
+    input_gain_db: f32,
+    normalize: NormalizeMode,
+    dc_filter: bool,
+    denoise: bool,
     debug_audio: bool,
     debug_vad: bool,
     list_devices: bool,
@@ -186,6 +366,64 @@ impl Config {
             file.model_size.unwrap_or(cli.model_size)
         };
 
+        let models = file
+            .models
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, model)| (name, model.path))
+            .collect();
+
+        let languages = file
+            .languages
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(language, mapping)| (language.trim().to_ascii_lowercase(), mapping.model))
+            .collect();
+
+        let model_idle_unload_ms =
+            if matches.value_source("model_idle_unload_ms") == Some(ValueSource::CommandLine) {
+                cli.model_idle_unload_ms
+            } else {
+                file.model_idle_unload_ms
+                    .unwrap_or(cli.model_idle_unload_ms)
+            };
+
+        let model_memory_budget_mb =
+            if matches.value_source("model_memory_budget_mb") == Some(ValueSource::CommandLine) {
+                cli.model_memory_budget_mb
+            } else {
+                file.model_memory_budget_mb
+                    .unwrap_or(cli.model_memory_budget_mb)
+            };
+
+        let model_auto_benchmark =
+            if matches.value_source("model_auto_benchmark") == Some(ValueSource::CommandLine) {
+                cli.model_auto_benchmark
+            } else {
+                file.model_auto_benchmark
+                    .unwrap_or(cli.model_auto_benchmark)
+            };
+
+        let model_auto_target_rtf =
+            if matches.value_source("model_auto_target_rtf") == Some(ValueSource::CommandLine) {
+                cli.model_auto_target_rtf
+            } else {
+                file.model_auto_target_rtf
+                    .unwrap_or(cli.model_auto_target_rtf)
+            };
+
+        let gpu = if matches.value_source("gpu") == Some(ValueSource::CommandLine) {
+            cli.gpu
+        } else {
+            file.gpu.unwrap_or(cli.gpu)
+        };
+
+        let flash_attn = if matches.value_source("flash_attn") == Some(ValueSource::CommandLine) {
+            cli.flash_attn
+        } else {
+            file.flash_attn.unwrap_or(cli.flash_attn)
+        };
+
         let model_variants =
             if matches.value_source("model_variants") == Some(ValueSource::CommandLine) {
                 cli.model_variants
@@ -211,6 +449,19 @@ impl Config {
                 .unwrap_or_else(AudioHost::default_for_platform)
         };
 
+        let device_fallback =
+            if matches.value_source("device_fallback") == Some(ValueSource::CommandLine) {
+                cli.device_fallback
+            } else {
+                file.device_fallback.unwrap_or(cli.device_fallback)
+            };
+
+        let channel = if matches.value_source("channel") == Some(ValueSource::CommandLine) {
+            cli.channel
+        } else {
+            file.channel.unwrap_or(cli.channel)
+        };
+
         let sample_rate = if matches.value_source("sample_rate") == Some(ValueSource::CommandLine) {
             cli.sample_rate
         } else {
@@ -242,6 +493,9 @@ impl Config {
                 file.vad_silence_ms.unwrap_or(cli.vad_silence_ms)
             };
 
+        let vad_threshold_explicit = matches.value_source("vad_threshold")
+            == Some(ValueSource::CommandLine)
+            || file.vad_threshold.is_some();
         let vad_threshold =
             if matches.value_source("vad_threshold") == Some(ValueSource::CommandLine) {
                 cli.vad_threshold
@@ -256,6 +510,99 @@ impl Config {
             file.vad_chunk_ms.unwrap_or(cli.vad_chunk_ms)
         };
 
+        let vad_auto_stop =
+            if matches.value_source("vad_auto_stop") == Some(ValueSource::CommandLine) {
+                cli.vad_auto_stop
+            } else {
+                file.vad_auto_stop.unwrap_or(cli.vad_auto_stop)
+            };
+
+        let vad_backend = if matches.value_source("vad_backend") == Some(ValueSource::CommandLine) {
+            cli.vad_backend
+        } else {
+            file.vad_backend.unwrap_or(cli.vad_backend)
+        };
+
+        let vad_model = if matches.value_source("vad_model") == Some(ValueSource::CommandLine) {
+            cli.vad_model
+        } else {
+            cli.vad_model.or(file.vad_model)
+        };
+
+        let vad_probability =
+            if matches.value_source("vad_probability") == Some(ValueSource::CommandLine) {
+                cli.vad_probability
+            } else {
+                file.vad_probability.unwrap_or(cli.vad_probability)
+            };
+
+        let vad_auto_calibrate =
+            if matches.value_source("vad_auto_calibrate") == Some(ValueSource::CommandLine) {
+                cli.vad_auto_calibrate
+            } else {
+                file.vad_auto_calibrate.unwrap_or(cli.vad_auto_calibrate)
+            };
+
+        let vad_calibration_ms =
+            if matches.value_source("vad_calibration_ms") == Some(ValueSource::CommandLine) {
+                cli.vad_calibration_ms
+            } else {
+                file.vad_calibration_ms.unwrap_or(cli.vad_calibration_ms)
+            };
+
+        let audio_level_interval_ms =
+            if matches.value_source("audio_level_interval_ms") == Some(ValueSource::CommandLine) {
+                cli.audio_level_interval_ms
+            } else {
+                file.audio_level_interval_ms
+                    .unwrap_or(cli.audio_level_interval_ms)
+            };
+
+        let warm_capture = if matches.value_source("warm_capture") == Some(ValueSource::CommandLine)
+        {
+            cli.warm_capture
+        } else {
+            file.warm_capture.unwrap_or(cli.warm_capture)
+        };
+
+        let pre_roll_ms = if matches.value_source("pre_roll_ms") == Some(ValueSource::CommandLine) {
+            cli.pre_roll_ms
+        } else {
+            file.pre_roll_ms.unwrap_or(cli.pre_roll_ms)
+        };
+
+        let resample_quality =
+            if matches.value_source("resample_quality") == Some(ValueSource::CommandLine) {
+                cli.resample_quality
+            } else {
+                file.resample_quality.unwrap_or(cli.resample_quality)
+            };
+
+        let input_gain_db =
+            if matches.value_source("input_gain_db") == Some(ValueSource::CommandLine) {
+                cli.input_gain_db
+            } else {
+                file.input_gain_db.unwrap_or(cli.input_gain_db)
+            };
+
+        let normalize = if matches.value_source("normalize") == Some(ValueSource::CommandLine) {
+            cli.normalize
+        } else {
+            file.normalize.unwrap_or(cli.normalize)
+        };
+
+        let dc_filter = if matches.value_source("dc_filter") == Some(ValueSource::CommandLine) {
+            cli.dc_filter
+        } else {
+            file.dc_filter.unwrap_or(cli.dc_filter)
+        };
+
+        let denoise = if matches.value_source("denoise") == Some(ValueSource::CommandLine) {
+            cli.denoise
+        } else {
+            file.denoise.unwrap_or(cli.denoise)
+        };
+
         let debug_audio = if matches.value_source("debug_audio") == Some(ValueSource::CommandLine) {
             cli.debug_audio
         } else {
@@ -288,21 +635,56 @@ impl Config {
                 file.download_model.unwrap_or(cli.download_model)
             };
 
+        let verify_model_checksum =
+            if matches.value_source("verify_model_checksum") == Some(ValueSource::CommandLine) {
+                cli.verify_model_checksum
+            } else {
+                file.verify_model_checksum
+                    .unwrap_or(cli.verify_model_checksum)
+            };
+
         Self {
             model_size,
+            models,
+            languages,
             model_variants,
+            model_idle_unload_ms,
+            model_memory_budget_mb,
+            model_auto_benchmark,
+            model_auto_target_rtf,
+            gpu,
+            flash_attn,
             download_model,
+            verify_model_checksum,
             language,
             toggle_language,
             device,
             audio_host,
+            device_fallback,
+            channel,
             sample_rate,
             format,
             mode,
             vad,
             vad_silence_ms,
             vad_threshold,
+            device_thresholds: HashMap::new(),
             vad_chunk_ms,
+            vad_auto_stop,
+            vad_backend,
+            vad_model,
+            vad_probability,
+            vad_auto_calibrate,
+            vad_calibration_ms,
+            vad_threshold_explicit,
+            audio_level_interval_ms,
+            warm_capture,
+            pre_roll_ms,
+            resample_quality,
+            input_gain_db,
+            normalize,
+            dc_filter,
+            denoise,
             debug_audio,
             debug_vad,
             list_devices,
@@ -314,12 +696,23 @@ impl Config {
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 struct FileConfig {
-    model_size: Option<ModelSize>,
+    model_size: Option<ModelRef>,
+    models: Option<HashMap<String, FileModelConfig>>,
+    languages: Option<HashMap<String, FileLanguageConfig>>,
     model_variants: Option<ModelVariants>,
+    model_idle_unload_ms: Option<u64>,
+    model_memory_budget_mb: Option<u64>,
+    model_auto_benchmark: Option<bool>,
+    model_auto_target_rtf: Option<f32>,
+    gpu: Option<GpuSelection>,
+    flash_attn: Option<bool>,
     download_model: Option<bool>,
+    verify_model_checksum: Option<bool>,
     language: Option<String>,
     device: Option<String>,
     audio_host: Option<AudioHost>,
+    device_fallback: Option<DeviceFallback>,
+    channel: Option<ChannelSelection>,
     sample_rate: Option<u32>,
     format: Option<OutputFormat>,
     mode: Option<OutputMode>,
@@ -327,12 +720,36 @@ struct FileConfig {
     vad_silence_ms: Option<u64>,
     vad_threshold: Option<f32>,
     vad_chunk_ms: Option<u64>,
+    vad_auto_stop: Option<bool>,
+    vad_backend: Option<VadBackend>,
+    vad_model: Option<PathBuf>,
+    vad_probability: Option<f32>,
+    vad_auto_calibrate: Option<bool>,
+    vad_calibration_ms: Option<u64>,
+    audio_level_interval_ms: Option<u64>,
+    warm_capture: Option<bool>,
+    pre_roll_ms: Option<u64>,
+    resample_quality: Option<ResampleQuality>,
+    input_gain_db: Option<f32>,
+    normalize: Option<NormalizeMode>,
+    dc_filter: Option<bool>,
+    denoise: Option<bool>,
     debug_audio: Option<bool>,
     debug_vad: Option<bool>,
     list_devices: Option<bool>,
     dump_audio: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+struct FileModelConfig {
+    path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileLanguageConfig {
+    model: ModelRef,
+}
+
 fn main() {
     let matches = Cli::command().get_matches();
     let cli = Cli::from_arg_matches(&matches).expect("Failed to parse CLI arguments");
@@ -343,9 +760,11 @@ fn main() {
             match daemon::send_status_command() {
                 Ok(response) => {
                     println!(
-                        "state={} language={}",
+                        "state={} language={} device={} backend={}",
                         response.state.as_deref().unwrap_or("unknown"),
-                        response.language.as_deref().unwrap_or("unknown")
+                        response.language.as_deref().unwrap_or("unknown"),
+                        response.device.as_deref().unwrap_or("unknown"),
+                        response.backend.as_deref().unwrap_or("unknown")
                     );
                 }
                 Err(err) => {
@@ -369,7 +788,26 @@ fn main() {
             }
             return;
         }
-        CliMode::Toggle | CliMode::RunDaemon | CliMode::ListDevices => {}
+        CliMode::SetDevice { device } => {
+            match daemon::send_set_device_command(device) {
+                Ok(response) => {
+                    println!(
+                        "device={}",
+                        response.device.as_deref().unwrap_or(device.as_str())
+                    );
+                }
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    process::exit(err.exit_code());
+                }
+            }
+            return;
+        }
+        CliMode::Toggle
+        | CliMode::RunDaemon
+        | CliMode::ListDevices
+        | CliMode::Calibrate { .. }
+        | CliMode::Model { .. } => {}
     }
 
     let file_config = match load_config_file() {
@@ -393,63 +831,317 @@ fn main() {
             }
             return;
         }
-        CliMode::RunDaemon | CliMode::ListDevices => {}
-        CliMode::StatusDaemon | CliMode::StopDaemon | CliMode::SetLanguage { .. } => unreachable!(),
+        CliMode::Model { command } => {
+            if let Err(err) = run_model_command(command, &config) {
+                eprintln!("error: {err}");
+                process::exit(err.exit_code());
+            }
+            return;
+        }
+        CliMode::RunDaemon | CliMode::ListDevices | CliMode::Calibrate { .. } => {}
+        CliMode::StatusDaemon
+        | CliMode::StopDaemon
+        | CliMode::SetLanguage { .. }
+        | CliMode::SetDevice { .. } => unreachable!(),
     }
 
     if mode == CliMode::RunDaemon {
         config.list_devices = false;
     }
 
+    if let CliMode::Calibrate { duration_ms } = mode {
+        if let Err(err) = run_calibrate(&config, duration_ms) {
+            eprintln!("error: {err}");
+            process::exit(err.exit_code());
+        }
+        return;
+    }
+
+    if config.list_devices {
+        if let Err(err) = run_list_devices(&config) {
+            eprintln!("error: {err}");
+            process::exit(err.exit_code());
+        }
+        return;
+    }
+
+    // The daemon picks the entry matching whichever device capture opens.
+    if !config.vad_threshold_explicit {
+        match CalibrationStore::load(&calibration::calibration_path()) {
+            Ok(store) => config.device_thresholds = store.thresholds(),
+            Err(err) => eprintln!("warning: {err}"),
+        }
+    }
+
     println!("SoundVibes sv {}", env!("CARGO_PKG_VERSION"));
-    println!("Model size: {:?}", config.model_size);
+    println!("Model size: {}", config.model_size);
     println!("Model variants: {:?}", config.model_variants);
+    println!(
+        "GPU: {} (flash attention: {})",
+        config.gpu, config.flash_attn
+    );
+    if config.model_idle_unload_ms > 0 || config.model_memory_budget_mb > 0 {
+        println!(
+            "Model unloading: idle {} ms, memory budget {} MB (0 = off)",
+            config.model_idle_unload_ms, config.model_memory_budget_mb
+        );
+    }
     println!("Language: {}", config.language);
     println!("Sample rate: {} Hz", config.sample_rate);
+    println!("Resample quality: {:?}", config.resample_quality);
+    println!(
+        "Input gain: {} dB (normalize: {:?}, DC filter: {}, denoise: {})",
+        config.input_gain_db, config.normalize, config.dc_filter, config.denoise
+    );
     println!("Format: {:?}", config.format);
     println!("Mode: {:?}", config.mode);
     println!("VAD: {:?}", config.vad);
     println!("VAD silence timeout: {} ms", config.vad_silence_ms);
     println!("VAD threshold: {:.4}", config.vad_threshold);
+    if !config.device_thresholds.is_empty() {
+        println!("VAD calibrated devices: {}", config.device_thresholds.len());
+    }
     println!("VAD chunk: {} ms", config.vad_chunk_ms);
+    println!("VAD auto-stop: {}", config.vad_auto_stop);
+    println!("VAD backend: {:?}", config.vad_backend);
+    println!("VAD auto-calibrate: {}", config.vad_auto_calibrate);
+    if config.warm_capture {
+        println!("Warm capture: pre-roll {} ms", config.pre_roll_ms);
+    }
     println!("Dump audio: {}", config.dump_audio);
     println!("Audio host: {:?}", config.audio_host);
+    println!("Input channel: {}", config.channel);
     if let Some(device) = &config.device {
         println!("Device: {device}");
+        println!("Device fallback: {:?}", config.device_fallback);
     }
 
-    let result = if config.list_devices {
-        run_list_devices(&config)
-    } else {
-        let daemon_config = daemon::DaemonConfig {
-            model_size: config.model_size,
-            model_variants: config.model_variants,
-            download_model: config.download_model,
-            language: config.language.clone(),
-            device: config.device.clone(),
-            audio_host: config.audio_host,
-            sample_rate: config.sample_rate,
-            format: config.format,
-            mode: config.mode,
-            vad: config.vad,
-            vad_silence_ms: config.vad_silence_ms,
-            vad_threshold: config.vad_threshold,
-            vad_chunk_ms: config.vad_chunk_ms,
-            debug_audio: config.debug_audio,
-            debug_vad: config.debug_vad,
-            dump_audio: config.dump_audio,
-        };
-        let deps = daemon::DaemonDeps::default();
-        let mut output = daemon::StdoutOutput;
-        daemon::run_daemon(&daemon_config, &deps, &mut output)
-    };
-
-    if let Err(err) = result {
+    let daemon_config = daemon_config(&config);
+    let deps = daemon::DaemonDeps::for_config(&daemon_config);
+    let mut output = daemon::StdoutOutput;
+    if let Err(err) = daemon::run_daemon(&daemon_config, &deps, &mut output) {
         eprintln!("error: {err}");
         process::exit(err.exit_code());
     }
 }
 
+fn daemon_config(config: &Config) -> daemon::DaemonConfig {
+    daemon::DaemonConfig {
+        model_size: config.model_size.clone(),
+        models: config.models.clone(),
+        languages: config.languages.clone(),
+        model_idle_unload_ms: config.model_idle_unload_ms,
+        model_memory_budget_mb: config.model_memory_budget_mb,
+        model_auto_benchmark: config.model_auto_benchmark,
+        model_auto_target_rtf: config.model_auto_target_rtf,
+        gpu: config.gpu,
+        flash_attn: config.flash_attn,
+        model_variants: config.model_variants,
+        download_model: config.download_model,
+        verify_model_checksum: config.verify_model_checksum,
+        language: config.language.clone(),
+        device: config.device.clone(),
+        audio_host: config.audio_host,
+        device_fallback: config.device_fallback,
+        channel: config.channel,
+        sample_rate: config.sample_rate,
+        format: config.format,
+        mode: config.mode,
+        vad: config.vad,
+        vad_silence_ms: config.vad_silence_ms,
+        vad_threshold: config.vad_threshold,
+        device_thresholds: config.device_thresholds.clone(),
+        vad_chunk_ms: config.vad_chunk_ms,
+        vad_auto_stop: config.vad_auto_stop,
+        vad_backend: config.vad_backend,
+        vad_model: config.vad_model.clone(),
+        vad_probability: config.vad_probability,
+        vad_auto_calibrate: config.vad_auto_calibrate,
+        vad_calibration_ms: config.vad_calibration_ms,
+        audio_level_interval_ms: config.audio_level_interval_ms,
+        warm_capture: config.warm_capture,
+        pre_roll_ms: config.pre_roll_ms,
+        resample_quality: config.resample_quality,
+        input_gain_db: config.input_gain_db,
+        normalize: config.normalize,
+        dc_filter: config.dc_filter,
+        denoise: config.denoise,
+        debug_audio: config.debug_audio,
+        debug_vad: config.debug_vad,
+        dump_audio: config.dump_audio,
+    }
+}
+
+fn run_model_command(command: &ModelCommand, config: &Config) -> Result<(), AppError> {
+    model::validate_aliases(&config.models)?;
+    let dir = model::default_model_dir();
+    match command {
+        ModelCommand::List => {
+            let installed = model::installed_models(&dir);
+            println!("Models in {}", dir.display());
+            let mut specs = ModelSpec::published();
+            specs.extend(
+                installed
+                    .iter()
+                    .map(|model| model.spec)
+                    .filter(|spec| !specs.contains(spec))
+                    .collect::<Vec<_>>(),
+            );
+            for spec in specs {
+                let size = installed
+                    .iter()
+                    .find(|model| model.spec == spec)
+                    .map(|model| format_model_bytes(model.bytes))
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "{:<22} {:<32} {size:>10}",
+                    spec.to_string(),
+                    spec.filename()
+                );
+            }
+            for (name, path) in sorted_aliases(&config.models) {
+                let size = fs::metadata(path)
+                    .map(|metadata| format_model_bytes(metadata.len()))
+                    .unwrap_or_else(|_| "-".to_string());
+                println!("{name:<22} {:<32} {size:>10}", path.display().to_string());
+            }
+            let mut models = installed
+                .iter()
+                .map(|model| model.spec.model_id())
+                .collect::<Vec<ModelId>>();
+            models.dedup();
+            for model_id in models {
+                let variants = model::satisfied_variants(&installed, model_id)
+                    .iter()
+                    .map(|variants| format!("{variants:?}").to_lowercase())
+                    .collect::<Vec<_>>();
+                println!(
+                    "{model_id}: satisfies model_variants {}",
+                    variants.join(", ")
+                );
+            }
+        }
+        ModelCommand::Pull { model } => {
+            let source = model_command_source(model, config)?;
+            let prepared = model::prepare_model(&source, true)?;
+            if prepared.downloaded {
+                println!("Downloaded {model} to {}", prepared.path.display());
+            } else {
+                println!("{model} already installed at {}", prepared.path.display());
+            }
+        }
+        ModelCommand::Rm { model } => match model_command_source(model, config)? {
+            ModelSource::Published(spec) => {
+                let path = model::remove_model(&spec)?;
+                println!("Removed {}", path.display());
+            }
+            ModelSource::Custom { name, path } => {
+                return Err(AppError::config(format!(
+                    "model '{name}' is a custom file at {}; remove it yourself and drop its [models.{name}] entry",
+                    path.display()
+                )));
+            }
+        },
+        ModelCommand::Verify { model } => {
+            let targets = match model {
+                Some(model) => vec![model_command_source(model, config)?],
+                None => model::installed_models(&dir)
+                    .into_iter()
+                    .map(|model| ModelSource::Published(model.spec))
+                    .chain(
+                        sorted_aliases(&config.models)
+                            .into_iter()
+                            .map(|(name, path)| ModelSource::Custom {
+                                name: name.clone(),
+                                path: path.clone(),
+                            }),
+                    )
+                    .collect(),
+            };
+            if targets.is_empty() {
+                println!("No models installed in {}", dir.display());
+            }
+            let mut failed = 0;
+            for source in targets {
+                let path = source.path();
+                let (label, verified) = match &source {
+                    ModelSource::Published(spec) => (
+                        spec.to_string(),
+                        model::verify_model(&path)
+                            .and_then(|()| model::verify_checksum(&path, &spec.filename())),
+                    ),
+                    // Custom files have no upstream checksum; only the header is checked.
+                    ModelSource::Custom { name, .. } => (
+                        name.clone(),
+                        model::verify_model(&path).map(|()| model::ChecksumStatus::Unknown),
+                    ),
+                };
+                match verified {
+                    Ok(model::ChecksumStatus::Verified) => {
+                        println!("ok {label} {} (sha256)", path.display())
+                    }
+                    Ok(model::ChecksumStatus::Unknown) => {
+                        println!("ok {label} {} (no checksum in manifest)", path.display())
+                    }
+                    Err(err) => {
+                        failed += 1;
+                        println!("failed {label}: {err}");
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::config(format!(
+                    "{failed} model(s) failed verification"
+                )));
+            }
+        }
+        ModelCommand::Path { model } => match model {
+            Some(model) => println!("{}", model_command_source(model, config)?.path().display()),
+            None => println!("{}", dir.display()),
+        },
+    }
+    Ok(())
+}
+
+// Model subcommands name exact files, so a bare size means the multilingual
+// model rather than the one `model_variants` would pick for a language.
+fn model_command_source(model: &ModelRef, config: &Config) -> Result<ModelSource, AppError> {
+    model.source(ModelLanguage::Auto, &config.models)
+}
+
+fn sorted_aliases(aliases: &HashMap<String, PathBuf>) -> Vec<(&String, &PathBuf)> {
+    let mut aliases = aliases.iter().collect::<Vec<_>>();
+    aliases.sort_unstable();
+    aliases
+}
+
+fn format_model_bytes(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn run_calibrate(config: &Config, duration_ms: u64) -> Result<(), AppError> {
+    let device_key = calibration::device_key(config.device.as_deref());
+    println!("Calibrating noise floor on '{device_key}' for {duration_ms} ms; stay quiet...");
+    let daemon_config = daemon_config(config);
+    let deps = daemon::DaemonDeps::for_config(&daemon_config);
+    let (device, result) = daemon::run_calibration(&daemon_config, &deps, duration_ms)?;
+    println!("Noise floor: {:.4}", result.noise_floor);
+    println!("VAD threshold: {:.4}", result.threshold);
+
+    // Store under the opened device's name, which is what the daemon looks up.
+    let device = device.or_else(|| config.device.clone());
+    let path = calibration::calibration_path();
+    let mut store = CalibrationStore::load(&path)?;
+    store.insert(device.as_deref(), &result, chrono::Utc::now().to_rfc3339());
+    store.save(&path)?;
+    println!(
+        "Saved calibration for '{}' to {}",
+        calibration::device_key(device.as_deref()),
+        path.display()
+    );
+    Ok(())
+}
+
 fn load_config_file() -> Result<FileConfig, AppError> {
     let path = match config_path() {
         Some(path) => path,
@@ -484,10 +1176,45 @@ fn config_path() -> Option<PathBuf> {
 fn run_list_devices(config: &Config) -> Result<(), AppError> {
     let host = daemon::select_audio_host(config.audio_host)?;
     audio::configure_alsa_logging(config.debug_audio);
-    let devices = audio::list_input_devices(&host).map_err(|err| AppError::audio(err.message))?;
+    let deps = daemon::DaemonDeps::for_config(&daemon_config(config));
+    let devices = deps
+        .audio
+        .describe_input_devices(&host)
+        .map_err(|err| AppError::audio(err.message))?;
+    if matches!(config.format, OutputFormat::Jsonl) {
+        for device in &devices {
+            let line = serde_json::to_string(device)
+                .map_err(|err| AppError::runtime(format!("failed to encode device: {err}")))?;
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
     println!("Input devices:");
-    for name in devices {
-        println!("  - {name}");
+    for device in devices {
+        let default = if device.is_default { " (default)" } else { "" };
+        println!("  - {}{default}", device.name);
+        if device.id != device.name {
+            println!("      id: {}", device.id);
+        }
+        if !device.sample_rates.is_empty() {
+            let rates: Vec<String> = device
+                .sample_rates
+                .iter()
+                .map(|rate| rate.to_string())
+                .collect();
+            let channels: Vec<String> = device
+                .channels
+                .iter()
+                .map(|channels| channels.to_string())
+                .collect();
+            println!(
+                "      rates: {} Hz; channels: {}; formats: {}",
+                rates.join(", "),
+                channels.join(", "),
+                device.sample_formats.join(", ")
+            );
+        }
     }
     Ok(())
 }
@@ -506,6 +1233,7 @@ mod tests {
     use std::path::Path;
     use std::sync::{Mutex, OnceLock};
     use std::thread;
+    use sv::model::ModelSize;
 
     static TEST_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
@@ -634,7 +1362,7 @@ mod tests {
     #[test]
     fn model_size_defaults_to_small() {
         let config = config_from_args_and_file(&["sv"], FileConfig::default());
-        assert_eq!(config.model_size, ModelSize::Small);
+        assert_eq!(config.model_size, ModelSize::Small.into());
     }
 
     #[test]
@@ -642,20 +1370,73 @@ mod tests {
         let config = config_from_args_and_file(
             &["sv"],
             FileConfig {
-                model_size: Some(ModelSize::Medium),
+                model_size: Some(ModelSize::Medium.into()),
                 ..FileConfig::default()
             },
         );
-        assert_eq!(config.model_size, ModelSize::Medium);
+        assert_eq!(config.model_size, ModelSize::Medium.into());
 
         let cli_override = config_from_args_and_file(
             &["sv", "--model-size", "tiny"],
             FileConfig {
-                model_size: Some(ModelSize::Large),
+                model_size: Some(ModelSize::Large.into()),
                 ..FileConfig::default()
             },
         );
-        assert_eq!(cli_override.model_size, ModelSize::Tiny);
+        assert_eq!(cli_override.model_size, ModelSize::Tiny.into());
+    }
+
+    #[test]
+    fn model_aliases_load_from_config_sections() {
+        let file: FileConfig = toml::from_str(
+            "model_size = \"work\"\n[models.work]\npath = \"/opt/models/ggml-work.bin\"\n",
+        )
+        .expect("parse config");
+        let config = config_from_args_and_file(&["sv"], file);
+        assert_eq!(config.model_size, ModelRef::Alias("work".to_string()));
+        assert_eq!(
+            config.models.get("work"),
+            Some(&PathBuf::from("/opt/models/ggml-work.bin"))
+        );
+
+        let quantized: FileConfig =
+            toml::from_str("model_size = \"large-v3-turbo-q5_0\"").expect("parse config");
+        let config = config_from_args_and_file(&["sv"], quantized);
+        assert_eq!(config.model_size.to_string(), "large-v3-turbo-q5_0");
+    }
+
+    #[test]
+    fn language_model_mappings_load_from_config_sections() {
+        let file: FileConfig = toml::from_str(
+            "[languages.en]\nmodel = \"small.en\"\n[languages.SV]\nmodel = \"medium\"\n",
+        )
+        .expect("parse config");
+        let config = config_from_args_and_file(&["sv"], file);
+        assert_eq!(
+            config.languages.get("en"),
+            Some(&ModelRef::Exact(ModelSpec::new(
+                ModelSize::Small,
+                ModelLanguage::En
+            )))
+        );
+        assert_eq!(config.languages.get("sv"), Some(&ModelSize::Medium.into()));
+    }
+
+    #[test]
+    fn gpu_settings_load_from_config_and_cli() {
+        let config = config_from_args_and_file(&["sv"], FileConfig::default());
+        assert_eq!(config.gpu, GpuSelection::Auto);
+        assert!(!config.flash_attn);
+
+        let file: FileConfig =
+            toml::from_str("gpu = 1\nflash_attn = true\n").expect("parse config");
+        let config = config_from_args_and_file(&["sv"], file);
+        assert_eq!(config.gpu, GpuSelection::Device(1));
+        assert!(config.flash_attn);
+
+        let file: FileConfig = toml::from_str("gpu = \"auto\"").expect("parse config");
+        let cli_override = config_from_args_and_file(&["sv", "--gpu", "off"], file);
+        assert_eq!(cli_override.gpu, GpuSelection::Off);
     }
 
     #[test]
@@ -691,6 +1472,99 @@ mod tests {
         assert_eq!(cli_override.model_variants, ModelVariants::En);
     }
 
+    #[test]
+    fn vad_auto_stop_respects_config_and_cli_override() {
+        let config = config_from_args_and_file(
+            &["sv"],
+            FileConfig {
+                vad_auto_stop: Some(true),
+                ..FileConfig::default()
+            },
+        );
+        assert!(config.vad_auto_stop);
+
+        let cli_override =
+            config_from_args_and_file(&["sv", "--vad-auto-stop"], FileConfig::default());
+        assert!(cli_override.vad_auto_stop);
+    }
+
+    #[test]
+    fn vad_backend_respects_config_and_cli_override() {
+        let config = config_from_args_and_file(
+            &["sv"],
+            FileConfig {
+                vad_backend: Some(VadBackend::Silero),
+                vad_probability: Some(0.7),
+                ..FileConfig::default()
+            },
+        );
+        assert_eq!(config.vad_backend, VadBackend::Silero);
+        assert_eq!(config.vad_probability, 0.7);
+
+        let cli_override = config_from_args_and_file(
+            &["sv", "--vad-backend", "energy"],
+            FileConfig {
+                vad_backend: Some(VadBackend::Silero),
+                ..FileConfig::default()
+            },
+        );
+        assert_eq!(cli_override.vad_backend, VadBackend::Energy);
+    }
+
+    #[test]
+    fn device_fallback_defaults_to_default_device() {
+        let config = config_from_args_and_file(&["sv"], FileConfig::default());
+        assert_eq!(config.device_fallback, DeviceFallback::Default);
+
+        let from_file = config_from_args_and_file(
+            &["sv"],
+            FileConfig {
+                device_fallback: Some(DeviceFallback::Off),
+                ..FileConfig::default()
+            },
+        );
+        assert_eq!(from_file.device_fallback, DeviceFallback::Off);
+    }
+
+    #[test]
+    fn channel_accepts_index_or_mode_from_config_and_cli() {
+        let config = config_from_args_and_file(&["sv"], FileConfig::default());
+        assert_eq!(config.channel, ChannelSelection::Average);
+
+        let from_file: FileConfig = toml::from_str("channel = 2").expect("parse config");
+        let config = config_from_args_and_file(&["sv"], from_file);
+        assert_eq!(config.channel, ChannelSelection::Index(2));
+
+        let cli_override = config_from_args_and_file(
+            &["sv", "--channel", "max-energy"],
+            FileConfig {
+                channel: Some(ChannelSelection::Index(2)),
+                ..FileConfig::default()
+            },
+        );
+        assert_eq!(cli_override.channel, ChannelSelection::MaxEnergy);
+        assert!(Cli::try_parse_from(["sv", "--channel", "0"]).is_err());
+    }
+
+    #[test]
+    fn vad_threshold_explicit_tracks_config_and_cli() {
+        let default = config_from_args_and_file(&["sv"], FileConfig::default());
+        assert!(!default.vad_threshold_explicit);
+
+        let from_file = config_from_args_and_file(
+            &["sv"],
+            FileConfig {
+                vad_threshold: Some(0.02),
+                ..FileConfig::default()
+            },
+        );
+        assert!(from_file.vad_threshold_explicit);
+
+        let from_cli =
+            config_from_args_and_file(&["sv", "--vad-threshold", "0.02"], FileConfig::default());
+        assert!(from_cli.vad_threshold_explicit);
+    }
+
     #[test]
     fn toggle_language_cli_override_is_captured() {
         let config =
@@ -700,6 +1574,16 @@ mod tests {
         assert_eq!(config.toggle_language.as_deref(), Some("de"));
     }
 
+    #[test]
+    fn parses_calibrate_subcommand() {
+        let cli = Cli::try_parse_from(["sv", "calibrate", "--duration-ms", "1500"])
+            .expect("failed to parse cli");
+        assert_eq!(
+            resolve_cli_mode(&cli),
+            CliMode::Calibrate { duration_ms: 1500 }
+        );
+    }
+
     #[test]
     fn parses_daemon_start_subcommand() {
         let cli = Cli::try_parse_from(["sv", "daemon", "start"]).expect("failed to parse cli");
@@ -729,4 +1613,100 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn parses_model_subcommands() {
+        let cli = Cli::parse_from(["sv", "model", "pull", "small.en"]);
+        assert_eq!(
+            resolve_cli_mode(&cli),
+            CliMode::Model {
+                command: ModelCommand::Pull {
+                    model: ModelRef::Exact(ModelSpec::new(ModelSize::Small, ModelLanguage::En)),
+                },
+            }
+        );
+
+        let cli = Cli::parse_from(["sv", "model", "path", "medium"]);
+        assert_eq!(
+            resolve_cli_mode(&cli),
+            CliMode::Model {
+                command: ModelCommand::Path {
+                    model: Some(ModelRef::Builtin(ModelSize::Medium.into())),
+                },
+            }
+        );
+
+        let cli = Cli::parse_from(["sv", "model", "pull", "work"]);
+        assert_eq!(
+            resolve_cli_mode(&cli),
+            CliMode::Model {
+                command: ModelCommand::Pull {
+                    model: ModelRef::Alias("work".to_string()),
+                },
+            }
+        );
+
+        assert!(Cli::try_parse_from(["sv", "model", "pull", "huge.en"]).is_err());
+    }
+
+    #[test]
+    fn model_subcommands_resolve_config_aliases() {
+        let file: FileConfig =
+            toml::from_str("[models.work]\npath = \"/nonexistent/ggml-work.bin\"\n")
+                .expect("parse config");
+        let config = config_from_args_and_file(&["sv"], file);
+
+        assert_eq!(
+            model_command_source(&ModelRef::Alias("work".to_string()), &config)
+                .expect("alias resolves"),
+            ModelSource::Custom {
+                name: "work".to_string(),
+                path: PathBuf::from("/nonexistent/ggml-work.bin"),
+            }
+        );
+        assert_eq!(
+            model_command_source(&ModelRef::Builtin(ModelSize::Small.into()), &config)
+                .expect("size resolves"),
+            ModelSource::Published(ModelSpec::new(ModelSize::Small, ModelLanguage::Auto))
+        );
+
+        let pull = ModelCommand::Pull {
+            model: ModelRef::Alias("work".to_string()),
+        };
+        let err = run_model_command(&pull, &config).expect_err("missing custom file");
+        assert!(err.to_string().contains("model file for 'work' not found"));
+
+        let rm = ModelCommand::Rm {
+            model: ModelRef::Alias("work".to_string()),
+        };
+        let err = run_model_command(&rm, &config).expect_err("custom files are not removed");
+        assert!(err.to_string().contains("custom file"));
+
+        let unknown = ModelCommand::Path {
+            model: Some(ModelRef::Alias("home".to_string())),
+        };
+        let err = run_model_command(&unknown, &config).expect_err("unknown alias");
+        assert!(err.to_string().contains("configured aliases: work"));
+    }
+
+    #[test]
+    fn model_subcommands_reject_invalid_aliases() {
+        let file: FileConfig = toml::from_str("[models.small]\npath = \"/opt/ggml-small.bin\"\n")
+            .expect("parse config");
+        let config = config_from_args_and_file(&["sv"], file);
+        let err = run_model_command(&ModelCommand::List, &config).expect_err("invalid alias");
+        assert!(err.to_string().contains("invalid model alias 'small'"));
+    }
+
+    #[test]
+    fn parses_daemon_set_device_subcommand() {
+        let cli = Cli::try_parse_from(["sv", "daemon", "set-device", "USB Mic"])
+            .expect("failed to parse cli");
+        assert_eq!(
+            resolve_cli_mode(&cli),
+            CliMode::SetDevice {
+                device: "USB Mic".to_string(),
+            }
+        );
+    }
 }
@@ -1,16 +1,46 @@
 use clap::ValueEnum;
+use ring::digest;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::error::AppError;
 
 const DEFAULT_MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+const DEFAULT_VAD_MODEL_BASE_URL: &str = "https://huggingface.co/ggml-org/whisper-vad/resolve/main";
+pub const VAD_MODEL_FILENAME: &str = "ggml-silero-v5.1.2.bin";
+// "ggml" as a little-endian u32, the first four bytes of every whisper.cpp model.
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+const DOWNLOAD_RETRY_DELAY: Duration = Duration::from_millis(500);
+const DOWNLOAD_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const PROGRESS_BAR_WIDTH: u64 = 30;
+// Built-in manifest of published model digests, in `sha256sum` format.
+const CHECKSUM_MANIFEST: &str = include_str!("model_checksums.txt");
+const DEFAULT_DISTIL_MODEL_BASE_URL: &str =
+    "https://huggingface.co/distil-whisper/distil-large-v3-ggml/resolve/main";
+const MODEL_SIZES: [ModelSize; 10] = [
+    ModelSize::Tiny,
+    ModelSize::Base,
+    ModelSize::Small,
+    ModelSize::Medium,
+    ModelSize::Large,
+    ModelSize::LargeV1,
+    ModelSize::LargeV2,
+    ModelSize::LargeV3,
+    ModelSize::LargeV3Turbo,
+    ModelSize::DistilLargeV3,
+];
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ModelSize {
     Auto,
     Tiny,
@@ -18,6 +48,11 @@ pub enum ModelSize {
     Small,
     Medium,
     Large,
+    LargeV1,
+    LargeV2,
+    LargeV3,
+    LargeV3Turbo,
+    DistilLargeV3,
 }
 
 impl ModelSize {
@@ -30,12 +65,272 @@ impl ModelSize {
 
     fn as_str(self) -> &'static str {
         match self {
-            ModelSize::Auto => "small",
+            ModelSize::Auto => "auto",
             ModelSize::Tiny => "tiny",
             ModelSize::Base => "base",
             ModelSize::Small => "small",
             ModelSize::Medium => "medium",
             ModelSize::Large => "large",
+            ModelSize::LargeV1 => "large-v1",
+            ModelSize::LargeV2 => "large-v2",
+            ModelSize::LargeV3 => "large-v3",
+            ModelSize::LargeV3Turbo => "large-v3-turbo",
+            ModelSize::DistilLargeV3 => "distil-large-v3",
+        }
+    }
+
+    // Only tiny through medium are published as English-only `.en` files; the
+    // large, turbo and distil models serve English from the multilingual file.
+    pub fn has_english_variant(self) -> bool {
+        matches!(
+            self.resolved(),
+            ModelSize::Tiny | ModelSize::Base | ModelSize::Small | ModelSize::Medium
+        )
+    }
+
+    // The distil-whisper repository only publishes the full-precision file.
+    pub fn has_quantized_variants(self) -> bool {
+        self.resolved() != ModelSize::DistilLargeV3
+    }
+}
+
+impl fmt::Display for ModelSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Quantization {
+    Q5_0,
+    Q5_1,
+    Q8_0,
+}
+
+impl Quantization {
+    const ALL: [Quantization; 3] = [Quantization::Q5_0, Quantization::Q5_1, Quantization::Q8_0];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Quantization::Q5_0 => "q5_0",
+            Quantization::Q5_1 => "q5_1",
+            Quantization::Q8_0 => "q8_0",
+        }
+    }
+
+    // Splits a trailing `-q5_0`/`-q5_1`/`-q8_0` off a model identifier.
+    fn strip_suffix(value: &str) -> (&str, Option<Quantization>) {
+        Quantization::ALL
+            .into_iter()
+            .find_map(|quantization| {
+                value
+                    .strip_suffix(quantization.as_str())
+                    .and_then(|rest| rest.strip_suffix('-'))
+                    .map(|rest| (rest, Some(quantization)))
+            })
+            .unwrap_or((value, None))
+    }
+}
+
+// A model size plus optional quantization, e.g. `small`, `large-v3-turbo` or
+// `medium-q5_0`. The English-only variant is picked per language on top of it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ModelId {
+    pub size: ModelSize,
+    pub quantization: Option<Quantization>,
+}
+
+impl From<ModelSize> for ModelId {
+    fn from(size: ModelSize) -> Self {
+        Self {
+            size,
+            quantization: None,
+        }
+    }
+}
+
+impl FromStr for ModelId {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (size, quantization) = Quantization::strip_suffix(value.trim());
+        let size = <ModelSize as ValueEnum>::from_str(size, true).map_err(|_| {
+            format!(
+                "unknown model size '{value}' (expected <size>[-q5_0|-q5_1|-q8_0], e.g. small or large-v3-turbo-q5_0)"
+            )
+        })?;
+        check_quantization(size, quantization)?;
+        Ok(Self { size, quantization })
+    }
+}
+
+fn check_quantization(size: ModelSize, quantization: Option<Quantization>) -> Result<(), String> {
+    match quantization {
+        Some(quantization) if !size.has_quantized_variants() => Err(format!(
+            "model '{size}' has no {} variant; use '{size}'",
+            quantization.as_str()
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl TryFrom<String> for ModelId {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for ModelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.size)?;
+        if let Some(quantization) = self.quantization {
+            write!(f, "-{}", quantization.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+// A model as named in config: a built-in model (whose English-only file is
+// picked per language), one exact published file such as `small.en`, or the
+// name of a `[models.<name>]` entry pointing at a custom ggml file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ModelRef {
+    Builtin(ModelId),
+    Exact(ModelSpec),
+    Alias(String),
+}
+
+impl ModelRef {
+    // The file serving `language`; built-in names win over aliases.
+    pub fn source(
+        &self,
+        language: ModelLanguage,
+        aliases: &HashMap<String, PathBuf>,
+    ) -> Result<ModelSource, AppError> {
+        match self {
+            ModelRef::Builtin(model) => Ok(ModelSource::Published(ModelSpec::for_model(
+                *model, language,
+            ))),
+            ModelRef::Exact(spec) => Ok(ModelSource::Published(*spec)),
+            ModelRef::Alias(name) => {
+                let path = aliases.get(name).ok_or_else(|| {
+                    let mut known = aliases.keys().map(String::as_str).collect::<Vec<_>>();
+                    known.sort_unstable();
+                    AppError::config(format!(
+                        "unknown model '{name}' (expected a model size or a [models.<name>] alias; configured aliases: {})",
+                        if known.is_empty() { "none".to_string() } else { known.join(", ") }
+                    ))
+                })?;
+                Ok(ModelSource::Custom {
+                    name: name.clone(),
+                    path: path.clone(),
+                })
+            }
+        }
+    }
+}
+
+impl From<ModelSize> for ModelRef {
+    fn from(size: ModelSize) -> Self {
+        ModelRef::Builtin(size.into())
+    }
+}
+
+impl From<ModelId> for ModelRef {
+    fn from(model: ModelId) -> Self {
+        ModelRef::Builtin(model)
+    }
+}
+
+impl FromStr for ModelRef {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        match value.parse::<ModelId>() {
+            Ok(model) => return Ok(ModelRef::Builtin(model)),
+            // A known size with an unpublished quantization is an error, not an alias.
+            Err(err) if is_model_size(Quantization::strip_suffix(value).0) => return Err(err),
+            Err(_) => {}
+        }
+        if value.contains(".en") {
+            return value.parse::<ModelSpec>().map(ModelRef::Exact);
+        }
+        if is_alias_name(value) {
+            return Ok(ModelRef::Alias(value.to_string()));
+        }
+        value.parse::<ModelId>().map(ModelRef::Builtin)
+    }
+}
+
+impl TryFrom<String> for ModelRef {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for ModelRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelRef::Builtin(model) => write!(f, "{model}"),
+            ModelRef::Exact(spec) => write!(f, "{spec}"),
+            ModelRef::Alias(name) => f.write_str(name),
+        }
+    }
+}
+
+fn is_model_size(value: &str) -> bool {
+    <ModelSize as ValueEnum>::from_str(value, true).is_ok()
+}
+
+fn is_alias_name(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+// Aliases named like a built-in model could never be selected.
+pub fn validate_aliases(aliases: &HashMap<String, PathBuf>) -> Result<(), AppError> {
+    let mut names = aliases.keys().collect::<Vec<_>>();
+    names.sort_unstable();
+    for name in names {
+        if name.parse::<ModelId>().is_ok() || !is_alias_name(name) {
+            return Err(AppError::config(format!(
+                "invalid model alias '{name}': use letters, digits, '-' or '_' and avoid built-in model names"
+            )));
+        }
+    }
+    Ok(())
+}
+
+// A resolved model file: published upstream (and downloadable) or a custom
+// file registered under an alias.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ModelSource {
+    Published(ModelSpec),
+    Custom { name: String, path: PathBuf },
+}
+
+impl ModelSource {
+    // The name reported in events: the model identifier or the alias.
+    pub fn name(&self) -> String {
+        match self {
+            ModelSource::Published(spec) => spec.model_id().to_string(),
+            ModelSource::Custom { name, .. } => name.clone(),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        match self {
+            ModelSource::Published(spec) => default_model_dir().join(spec.filename()),
+            ModelSource::Custom { path, .. } => path.clone(),
         }
     }
 }
@@ -84,24 +379,201 @@ pub fn model_language_for_transcription(language: &str) -> ModelLanguage {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct ModelSpec {
     pub size: ModelSize,
     pub language: ModelLanguage,
+    pub quantization: Option<Quantization>,
 }
 
 impl ModelSpec {
     pub fn new(size: ModelSize, language: ModelLanguage) -> Self {
-        Self { size, language }
+        Self::for_model(ModelId::from(size), language)
+    }
+
+    // Sizes without an English-only file resolve `En` to the multilingual model.
+    pub fn for_model(model: ModelId, language: ModelLanguage) -> Self {
+        let language = if model.size.has_english_variant() {
+            language
+        } else {
+            ModelLanguage::Auto
+        };
+        Self {
+            size: model.size,
+            language,
+            quantization: model.quantization,
+        }
+    }
+
+    pub fn model_id(&self) -> ModelId {
+        ModelId {
+            size: self.size,
+            quantization: self.quantization,
+        }
     }
 
     pub fn filename(&self) -> String {
-        let size = self.size.resolved().as_str();
-        match self.language {
-            ModelLanguage::Auto => format!("ggml-{size}.bin"),
-            ModelLanguage::En => format!("ggml-{size}.en.bin"),
+        format!("ggml-{self}.bin")
+    }
+
+    // Where the file is published; distil models live outside the whisper.cpp repo.
+    fn default_base_url(&self) -> &'static str {
+        match self.size {
+            ModelSize::DistilLargeV3 => DEFAULT_DISTIL_MODEL_BASE_URL,
+            _ => DEFAULT_MODEL_BASE_URL,
         }
     }
+
+    // The unquantized models published upstream; quantized files are listed
+    // once installed.
+    pub fn published() -> Vec<ModelSpec> {
+        MODEL_SIZES
+            .iter()
+            .flat_map(|size| {
+                [ModelLanguage::En, ModelLanguage::Auto]
+                    .into_iter()
+                    .filter(move |language| {
+                        *language == ModelLanguage::Auto || size.has_english_variant()
+                    })
+                    .map(move |language| ModelSpec::new(*size, language))
+            })
+            .collect()
+    }
+
+    // Recognises the files `filename` produces, e.g. `ggml-small.en-q5_1.bin`.
+    pub fn from_filename(filename: &str) -> Option<ModelSpec> {
+        let name = filename.strip_prefix("ggml-")?.strip_suffix(".bin")?;
+        let spec = name.parse::<ModelSpec>().ok()?;
+        (spec.filename() == filename).then_some(spec)
+    }
+}
+
+impl fmt::Display for ModelSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.size.resolved())?;
+        if self.language == ModelLanguage::En {
+            f.write_str(".en")?;
+        }
+        if let Some(quantization) = self.quantization {
+            write!(f, "-{}", quantization.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+// Parses `<size>[.en][-<quantization>]`, e.g. `small`, `small.en-q5_1` or
+// `large-v3-turbo-q8_0`.
+impl FromStr for ModelSpec {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, quantization) = Quantization::strip_suffix(value.trim());
+        let (size, language) = match name.strip_suffix(".en") {
+            Some(size) => (size, ModelLanguage::En),
+            None => (name, ModelLanguage::Auto),
+        };
+        let size = <ModelSize as ValueEnum>::from_str(size, true).map_err(|_| {
+            format!("unknown model '{value}' (expected <size>[.en][-<quantization>], e.g. small.en or large-v3-turbo-q5_0)")
+        })?;
+        if language == ModelLanguage::En && !size.has_english_variant() {
+            return Err(format!(
+                "model '{size}' has no English-only variant; use '{size}'"
+            ));
+        }
+        check_quantization(size, quantization)?;
+        Ok(ModelSpec {
+            size,
+            language,
+            quantization,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct InstalledModel {
+    pub spec: ModelSpec,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+pub fn installed_models(dir: &Path) -> Vec<InstalledModel> {
+    let mut installed = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let spec = ModelSpec::from_filename(entry.file_name().to_str()?)?;
+            let metadata = entry
+                .metadata()
+                .ok()
+                .filter(|metadata| metadata.is_file())?;
+            Some(InstalledModel {
+                spec,
+                path: entry.path(),
+                bytes: metadata.len(),
+            })
+        })
+        .collect::<Vec<_>>();
+    installed.sort_by_key(|model| {
+        (
+            MODEL_SIZES.iter().position(|size| *size == model.spec.size),
+            model.spec.quantization.map(Quantization::as_str),
+            model.spec.language == ModelLanguage::Auto,
+        )
+    });
+    installed
+}
+
+// The `model_variants` settings `model` can serve from the installed files
+// without downloading anything.
+pub fn satisfied_variants(installed: &[InstalledModel], model: ModelId) -> Vec<ModelVariants> {
+    let available = |language| {
+        installed
+            .iter()
+            .any(|installed| installed.spec == ModelSpec::for_model(model, language))
+    };
+    [
+        ModelVariants::En,
+        ModelVariants::Multilingual,
+        ModelVariants::Both,
+    ]
+    .into_iter()
+    .filter(|variants| {
+        variants
+            .preload()
+            .iter()
+            .all(|language| available(*language))
+    })
+    .collect()
+}
+
+pub fn remove_model(spec: &ModelSpec) -> Result<PathBuf, AppError> {
+    let path = default_model_dir().join(spec.filename());
+    if !path.is_file() {
+        return Err(AppError::config(format!(
+            "model {spec} is not installed at {}",
+            path.display()
+        )));
+    }
+    fs::remove_file(&path).map_err(|err| {
+        AppError::config(format!("failed to remove model {}: {err}", path.display()))
+    })?;
+    Ok(path)
+}
+
+// Checks that the file exists, is non-empty and carries the ggml header, which
+// catches missing, truncated-to-zero and non-model files (e.g. HTML error pages).
+pub fn verify_model(path: &Path) -> Result<(), AppError> {
+    validate_model_path(path)?;
+    let mut magic = [0u8; 4];
+    let header = fs::File::open(path).and_then(|mut file| file.read_exact(&mut magic));
+    if header.is_err() || magic != GGML_MAGIC {
+        return Err(AppError::config(format!(
+            "model file is not a ggml model: {}",
+            path.display()
+        )));
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -110,20 +582,64 @@ pub struct PreparedModel {
     pub downloaded: bool,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+}
+
 pub fn prepare_model(
-    explicit_path: Option<&Path>,
-    spec: &ModelSpec,
+    source: &ModelSource,
+    allow_download: bool,
+) -> Result<PreparedModel, AppError> {
+    prepare_model_with_progress(source, allow_download, &mut |_| {})
+}
+
+// Like `prepare_model`, reporting download progress (throttled) to `progress`.
+// Custom files are never downloaded; they only have to look like ggml models.
+pub fn prepare_model_with_progress(
+    source: &ModelSource,
     allow_download: bool,
+    progress: &mut dyn FnMut(DownloadProgress),
 ) -> Result<PreparedModel, AppError> {
-    let path = resolve_model_path(explicit_path, spec);
-    let downloaded = ensure_model_available(&path, spec, allow_download)?;
+    let path = source.path();
+    let downloaded = match source {
+        ModelSource::Published(spec) => ensure_file_available(&path, allow_download, || {
+            download_model(&path, spec, progress)
+        })?,
+        ModelSource::Custom { name, .. } => {
+            if !path.exists() {
+                return Err(AppError::config(format!(
+                    "model file for '{name}' not found at {}",
+                    path.display()
+                )));
+            }
+            verify_model(&path)?;
+            false
+        }
+    };
     Ok(PreparedModel { path, downloaded })
 }
 
-pub fn resolve_model_path(explicit_path: Option<&Path>, spec: &ModelSpec) -> PathBuf {
-    explicit_path
+pub fn prepare_vad_model(
+    explicit_path: Option<&Path>,
+    allow_download: bool,
+) -> Result<PreparedModel, AppError> {
+    let path = explicit_path
         .map(Path::to_path_buf)
-        .unwrap_or_else(|| default_model_dir().join(spec.filename()))
+        .unwrap_or_else(|| default_model_dir().join(VAD_MODEL_FILENAME));
+    let downloaded = ensure_file_available(&path, allow_download, || {
+        let base = env::var("SV_VAD_MODEL_BASE_URL")
+            .unwrap_or_else(|_| DEFAULT_VAD_MODEL_BASE_URL.to_string());
+        download_file(
+            &path,
+            VAD_MODEL_FILENAME,
+            &base,
+            CHECKSUM_MANIFEST,
+            &mut |_| {},
+        )
+    })?;
+    Ok(PreparedModel { path, downloaded })
 }
 
 pub fn default_model_dir() -> PathBuf {
@@ -134,10 +650,10 @@ pub fn default_model_dir() -> PathBuf {
     data_home.join("soundvibes").join("models")
 }
 
-fn ensure_model_available(
+fn ensure_file_available(
     path: &Path,
-    spec: &ModelSpec,
     allow_download: bool,
+    download: impl FnOnce() -> Result<(), AppError>,
 ) -> Result<bool, AppError> {
     if path.exists() {
         validate_model_path(path)?;
@@ -151,7 +667,7 @@ fn ensure_model_available(
         )));
     }
 
-    download_model(path, spec)?;
+    download()?;
     validate_model_path(path)?;
     Ok(true)
 }
@@ -172,9 +688,109 @@ fn validate_model_path(path: &Path) -> Result<(), AppError> {
     Ok(())
 }
 
-fn download_model(path: &Path, spec: &ModelSpec) -> Result<(), AppError> {
-    let filename = spec.filename();
-    let base = env::var("SV_MODEL_BASE_URL").unwrap_or_else(|_| DEFAULT_MODEL_BASE_URL.to_string());
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    Verified,
+    Unknown,
+}
+
+pub fn expected_checksum(filename: &str) -> Option<&'static str> {
+    manifest_checksum(CHECKSUM_MANIFEST, filename)
+}
+
+fn manifest_checksum<'a>(manifest: &'a str, filename: &str) -> Option<&'a str> {
+    manifest
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim().trim_start_matches('*') == filename)
+        .map(|(checksum, _)| checksum)
+}
+
+// Checks the file against the manifest entry for `filename`, if there is one.
+pub fn verify_checksum(path: &Path, filename: &str) -> Result<ChecksumStatus, AppError> {
+    verify_manifest_checksum(CHECKSUM_MANIFEST, path, filename)
+}
+
+fn verify_manifest_checksum(
+    manifest: &str,
+    path: &Path,
+    filename: &str,
+) -> Result<ChecksumStatus, AppError> {
+    match manifest_checksum(manifest, filename) {
+        Some(expected) => {
+            check_sha256(path, expected)?;
+            Ok(ChecksumStatus::Verified)
+        }
+        None => Ok(ChecksumStatus::Unknown),
+    }
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, AppError> {
+    let mut file = fs::File::open(path).map_err(|err| {
+        AppError::config(format!(
+            "failed to open model file {}: {err}",
+            path.display()
+        ))
+    })?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let read = file.read(&mut buffer).map_err(|err| {
+            AppError::config(format!(
+                "failed to read model file {}: {err}",
+                path.display()
+            ))
+        })?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+fn check_sha256(path: &Path, expected: &str) -> Result<(), AppError> {
+    let actual = sha256_file(path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(AppError::config(format!(
+            "model file {} is corrupt or incomplete (sha256 {actual}, expected {expected}); delete it and download it again",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+fn download_model(
+    path: &Path,
+    spec: &ModelSpec,
+    progress: &mut dyn FnMut(DownloadProgress),
+) -> Result<(), AppError> {
+    let base =
+        env::var("SV_MODEL_BASE_URL").unwrap_or_else(|_| spec.default_base_url().to_string());
+    download_file(path, &spec.filename(), &base, CHECKSUM_MANIFEST, progress)
+}
+
+enum DownloadError {
+    // Network drops, short reads and server errors; the `.part` file is kept
+    // and the next attempt resumes from its length.
+    Retry(AppError),
+    Fatal(AppError),
+}
+
+fn download_file(
+    path: &Path,
+    filename: &str,
+    base: &str,
+    manifest: &str,
+    progress: &mut dyn FnMut(DownloadProgress),
+) -> Result<(), AppError> {
     let url = format!("{}/{}", base.trim_end_matches('/'), filename);
 
     println!("Downloading model {filename} from {url}...");
@@ -188,28 +804,31 @@ fn download_model(path: &Path, spec: &ModelSpec) -> Result<(), AppError> {
         })?;
     }
 
-    let response = ureq::get(&url)
-        .call()
-        .map_err(|err| AppError::config(format!("failed to download model from {url}: {err}")))?;
-    if response.status() != 200 {
-        return Err(AppError::config(format!(
-            "model download failed with status {} from {url}",
-            response.status()
-        )));
+    let temp_path = path.with_extension("bin.part");
+    let mut attempt = 1;
+    loop {
+        match download_attempt(&url, &temp_path, progress) {
+            Ok(()) => break,
+            Err(DownloadError::Retry(err)) if attempt < DOWNLOAD_ATTEMPTS => {
+                let delay = DOWNLOAD_RETRY_DELAY * 2u32.pow(attempt - 1);
+                eprintln!(
+                    "warning: {err}; retrying in {:.1}s (attempt {}/{DOWNLOAD_ATTEMPTS})",
+                    delay.as_secs_f32(),
+                    attempt + 1
+                );
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(DownloadError::Retry(err) | DownloadError::Fatal(err)) => return Err(err),
+        }
     }
 
-    let temp_path = path.with_extension("bin.part");
-    let mut reader = response.into_reader();
-    let mut file = fs::File::create(&temp_path).map_err(|err| {
-        AppError::config(format!(
-            "failed to create temporary model file {}: {err}",
-            temp_path.display()
-        ))
-    })?;
-    io::copy(&mut reader, &mut file)
-        .map_err(|err| AppError::config(format!("failed to write model file: {err}")))?;
-    file.flush()
-        .map_err(|err| AppError::config(format!("failed to flush model file: {err}")))?;
+    if let Some(expected) = manifest_checksum(manifest, filename) {
+        if let Err(err) = check_sha256(&temp_path, expected) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(err);
+        }
+    }
     fs::rename(&temp_path, path).map_err(|err| {
         AppError::config(format!(
             "failed to move model file into place {}: {err}",
@@ -218,3 +837,283 @@ fn download_model(path: &Path, spec: &ModelSpec) -> Result<(), AppError> {
     })?;
     Ok(())
 }
+
+fn download_attempt(
+    url: &str,
+    temp_path: &Path,
+    progress: &mut dyn FnMut(DownloadProgress),
+) -> Result<(), DownloadError> {
+    let resume_from = fs::metadata(temp_path).map_or(0, |metadata| metadata.len());
+    let mut request = ureq::get(url);
+    if resume_from > 0 {
+        request = request.set("Range", &format!("bytes={resume_from}-"));
+    }
+    let response = match request.call() {
+        Ok(response) => response,
+        // The partial file does not fit the remote one (e.g. it changed); start over.
+        Err(ureq::Error::Status(416, _)) => {
+            let _ = fs::remove_file(temp_path);
+            return Err(DownloadError::Retry(AppError::config(format!(
+                "cannot resume model download from {url}; restarting"
+            ))));
+        }
+        Err(ureq::Error::Status(status, _)) => {
+            let err = AppError::config(format!(
+                "model download failed with status {status} from {url}"
+            ));
+            return Err(if status >= 500 {
+                DownloadError::Retry(err)
+            } else {
+                DownloadError::Fatal(err)
+            });
+        }
+        Err(err) => {
+            return Err(DownloadError::Retry(AppError::config(format!(
+                "failed to download model from {url}: {err}"
+            ))))
+        }
+    };
+
+    let resumed = response.status() == 206 && resume_from > 0;
+    if !resumed && response.status() != 200 {
+        return Err(DownloadError::Fatal(AppError::config(format!(
+            "model download failed with status {} from {url}",
+            response.status()
+        ))));
+    }
+    let offset = if resumed { resume_from } else { 0 };
+    if resumed {
+        println!("Resuming download at {resume_from} bytes");
+    }
+    let total_bytes = response
+        .header("Content-Length")
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(|len| len + offset);
+
+    let file = if resumed {
+        fs::OpenOptions::new().append(true).open(temp_path)
+    } else {
+        fs::File::create(temp_path)
+    };
+    let mut file = file.map_err(|err| {
+        DownloadError::Fatal(AppError::config(format!(
+            "failed to create temporary model file {}: {err}",
+            temp_path.display()
+        )))
+    })?;
+
+    let show_bar = io::stderr().is_terminal();
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut downloaded_bytes = offset;
+    let mut last_report = Instant::now();
+    let result = loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break Ok(()),
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                break Err(DownloadError::Retry(AppError::config(format!(
+                    "model download from {url} interrupted: {err}"
+                ))))
+            }
+        };
+        if let Err(err) = file.write_all(&buffer[..read]) {
+            break Err(DownloadError::Fatal(AppError::config(format!(
+                "failed to write model file: {err}"
+            ))));
+        }
+        downloaded_bytes += read as u64;
+        if last_report.elapsed() >= DOWNLOAD_PROGRESS_INTERVAL {
+            last_report = Instant::now();
+            report_progress(downloaded_bytes, total_bytes, show_bar, progress);
+        }
+    };
+    file.flush().map_err(|err| {
+        DownloadError::Fatal(AppError::config(format!(
+            "failed to flush model file: {err}"
+        )))
+    })?;
+    report_progress(downloaded_bytes, total_bytes, show_bar, progress);
+    if show_bar {
+        eprintln!();
+    }
+    result?;
+
+    if let Some(total_bytes) = total_bytes.filter(|total| *total != downloaded_bytes) {
+        return Err(DownloadError::Retry(AppError::config(format!(
+            "model download from {url} is incomplete: received {downloaded_bytes} of {total_bytes} bytes"
+        ))));
+    }
+    Ok(())
+}
+
+fn report_progress(
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+    show_bar: bool,
+    progress: &mut dyn FnMut(DownloadProgress),
+) {
+    progress(DownloadProgress {
+        downloaded_bytes,
+        total_bytes,
+    });
+    if !show_bar {
+        return;
+    }
+    let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+    match total_bytes.filter(|total| *total > 0) {
+        Some(total) => {
+            let filled = (downloaded_bytes.min(total) * PROGRESS_BAR_WIDTH / total) as usize;
+            eprint!(
+                "\r[{}{}] {:>3}% {:.1}/{:.1} MiB",
+                "#".repeat(filled),
+                " ".repeat(PROGRESS_BAR_WIDTH as usize - filled),
+                downloaded_bytes.min(total) * 100 / total,
+                mib(downloaded_bytes),
+                mib(total)
+            );
+        }
+        None => eprint!("\r{:.1} MiB", mib(downloaded_bytes)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quantized_and_large_model_identifiers() {
+        let spec = "small.en-q5_1"
+            .parse::<ModelSpec>()
+            .expect("quantized spec");
+        assert_eq!(spec.filename(), "ggml-small.en-q5_1.bin");
+        assert_eq!(spec.model_id().to_string(), "small-q5_1");
+
+        let turbo = "large-v3-turbo-q5_0".parse::<ModelId>().expect("turbo id");
+        assert_eq!(turbo.size, ModelSize::LargeV3Turbo);
+        assert_eq!(turbo.quantization, Some(Quantization::Q5_0));
+        let spec = ModelSpec::for_model(turbo, ModelLanguage::En);
+        assert_eq!(spec.filename(), "ggml-large-v3-turbo-q5_0.bin");
+
+        assert!("large-v3.en".parse::<ModelSpec>().is_err());
+        assert!("small-q4_0".parse::<ModelId>().is_err());
+        assert_eq!(
+            ModelSpec::from_filename("ggml-distil-large-v3.bin"),
+            Some(ModelSpec::new(
+                ModelSize::DistilLargeV3,
+                ModelLanguage::Auto
+            ))
+        );
+        assert_eq!(ModelSpec::from_filename("ggml-silero-v5.1.2.bin"), None);
+    }
+
+    #[test]
+    fn rejects_quantized_distil_models() {
+        for value in ["distil-large-v3-q5_0", "distil-large-v3-q8_0"] {
+            let err = value
+                .parse::<ModelId>()
+                .expect_err("no quantized distil id");
+            assert!(err.contains("has no"), "{err}");
+            assert!(value.parse::<ModelSpec>().is_err());
+            assert!(value.parse::<ModelRef>().is_err());
+        }
+        assert_eq!(
+            "distil-large-v3".parse::<ModelRef>(),
+            Ok(ModelRef::Builtin(ModelSize::DistilLargeV3.into()))
+        );
+        assert_eq!(
+            "work-q5_0".parse::<ModelRef>(),
+            Ok(ModelRef::Alias("work-q5_0".to_string()))
+        );
+    }
+
+    #[test]
+    fn manifest_lookup_matches_sha256sum_lines() {
+        let manifest = "# comment\n\
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  ggml-tiny.bin\n\
+            0000000000000000000000000000000000000000000000000000000000000000 *ggml-tiny.en.bin\n";
+        assert_eq!(
+            manifest_checksum(manifest, "ggml-tiny.bin"),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert!(manifest_checksum(manifest, "ggml-tiny.en.bin").is_some());
+        assert_eq!(manifest_checksum(manifest, "ggml-base.bin"), None);
+    }
+
+    #[test]
+    #[ignore = "needs the upstream digests from `mise run model-checksums`"]
+    fn manifest_covers_every_published_model() {
+        let missing = ModelSpec::published()
+            .into_iter()
+            .map(|spec| spec.filename())
+            .filter(|filename| expected_checksum(filename).is_none())
+            .collect::<Vec<_>>();
+        assert!(missing.is_empty(), "no manifest entry for {missing:?}");
+    }
+
+    #[test]
+    fn sha256_rejects_corrupt_files() {
+        let path = env::temp_dir().join(format!("soundvibes-{}-checksum.bin", std::process::id()));
+        fs::write(&path, b"abc").expect("write model file");
+
+        let checked = check_sha256(
+            &path,
+            "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+        );
+        let corrupt = check_sha256(
+            &path,
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        );
+        let _ = fs::remove_file(&path);
+
+        assert!(checked.is_ok());
+        let err = corrupt.expect_err("digest mismatch");
+        assert!(err.to_string().contains("corrupt or incomplete"));
+    }
+
+    #[test]
+    fn corrupt_downloads_and_installed_files_fail_their_manifest_digest() {
+        // The shipped manifest has no digests yet, so check against a test one
+        // whose entry does not match the served file.
+        let manifest =
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  ggml-tiny.bin\n";
+        let mut payload = GGML_MAGIC.to_vec();
+        payload.extend_from_slice(b"corrupted weights");
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let base = format!("http://{}", listener.local_addr().expect("server address"));
+        let served = payload.clone();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept download");
+            let _ = stream.read(&mut [0u8; 1024]);
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                served.len()
+            );
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(&served);
+        });
+
+        let dir = env::temp_dir().join(format!("soundvibes-{}-corrupt-model", std::process::id()));
+        let path = dir.join("ggml-tiny.bin");
+        let downloaded = download_file(&path, "ggml-tiny.bin", &base, manifest, &mut |_| {});
+        server.join().expect("test server");
+        let left_behind = path.exists() || path.with_extension("bin.part").exists();
+
+        fs::create_dir_all(&dir).expect("create model dir");
+        fs::write(&path, &payload).expect("write installed model");
+        let header = verify_model(&path);
+        let installed = verify_manifest_checksum(manifest, &path, "ggml-tiny.bin");
+        let unlisted = verify_manifest_checksum(manifest, &path, "ggml-base.bin");
+        let _ = fs::remove_dir_all(&dir);
+
+        let err = downloaded.expect_err("download digest mismatch");
+        assert!(err.to_string().contains("corrupt or incomplete"), "{err}");
+        assert!(!left_behind);
+        assert!(header.is_ok());
+        let err = installed.expect_err("installed digest mismatch");
+        assert!(err.to_string().contains("corrupt or incomplete"), "{err}");
+        assert_eq!(unlisted.ok(), Some(ChecksumStatus::Unknown));
+    }
+}
@@ -0,0 +1,240 @@
+use std::f32::consts::PI;
+
+use crate::audio::CLIPPING_LEVEL;
+use crate::denoise::SpectralGate;
+use crate::types::NormalizeMode;
+
+pub const DEFAULT_INPUT_GAIN_DB: f32 = 0.0;
+const DC_FILTER_CUTOFF_HZ: f32 = 20.0;
+const PEAK_TARGET_DBFS: f32 = -1.0;
+const RMS_TARGET_DBFS: f32 = -20.0;
+// Keeps normalization from turning near-silent recordings into loud noise.
+const MAX_NORMALIZE_GAIN_DB: f32 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClippingReport {
+    pub clipped_samples: usize,
+}
+
+// One-pole DC blocker, y[n] = x[n] - x[n-1] + r * y[n-1], that keeps its
+// state across blocks.
+pub struct DcFilter {
+    r: f32,
+    previous_input: Option<f32>,
+    previous_output: f32,
+}
+
+impl DcFilter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            r: (-2.0 * PI * DC_FILTER_CUTOFF_HZ / sample_rate.max(1) as f32).exp(),
+            previous_input: None,
+            previous_output: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, samples: &mut [f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        // Start from the mean of the first block so a large offset does not
+        // make the filter ring at the start of the stream.
+        let mut previous_input = self
+            .previous_input
+            .unwrap_or_else(|| samples.iter().sum::<f32>() / samples.len() as f32);
+        for sample in samples.iter_mut() {
+            let input = *sample;
+            self.previous_output = input - previous_input + self.r * self.previous_output;
+            previous_input = input;
+            *sample = self.previous_output;
+        }
+        self.previous_input = Some(previous_input);
+    }
+}
+
+// Conditions captured audio as it is drained, before VAD, the level meter,
+// calibration and auto-stop see it: DC filter, fixed gain, then the streaming
+// spectral gate. Clipped samples are counted until `take_clipping`.
+pub struct InputConditioner {
+    sample_rate: u32,
+    dc_filter: Option<DcFilter>,
+    gain_db: f32,
+    gate: Option<SpectralGate>,
+    clipped_samples: usize,
+    scratch: Vec<f32>,
+}
+
+impl InputConditioner {
+    pub fn new(sample_rate: u32, gain_db: f32, dc_filter: bool, denoise: bool) -> Self {
+        Self {
+            sample_rate,
+            dc_filter: dc_filter.then(|| DcFilter::new(sample_rate)),
+            gain_db,
+            gate: denoise.then(|| SpectralGate::new(sample_rate)),
+            clipped_samples: 0,
+            scratch: Vec::new(),
+        }
+    }
+
+    // Appends the conditioned `input` to `output`. With denoise on, the last
+    // few milliseconds stay held back until the next call or `flush`.
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        if input.is_empty() {
+            return;
+        }
+        self.scratch.clear();
+        self.scratch.extend_from_slice(input);
+        if let Some(filter) = self.dc_filter.as_mut() {
+            filter.process(&mut self.scratch);
+        }
+        if self.gain_db != 0.0 {
+            apply_gain(&mut self.scratch, self.gain_db);
+        }
+        // A sample counts once, whether the input already clipped or the gain did.
+        self.clipped_samples += input
+            .iter()
+            .zip(&self.scratch)
+            .filter(|(raw, conditioned)| {
+                raw.abs() >= CLIPPING_LEVEL || conditioned.abs() >= CLIPPING_LEVEL
+            })
+            .count();
+        match self.gate.as_mut() {
+            Some(gate) => gate.process(&self.scratch, output),
+            None => output.extend_from_slice(&self.scratch),
+        }
+    }
+
+    // Emits the audio still held back by the spectral gate.
+    pub fn flush(&mut self, output: &mut Vec<f32>) {
+        if let Some(gate) = self.gate.as_mut() {
+            gate.flush(output);
+        }
+    }
+
+    pub fn take_clipping(&mut self) -> Option<ClippingReport> {
+        let clipped_samples = std::mem::take(&mut self.clipped_samples);
+        (clipped_samples > 0).then_some(ClippingReport { clipped_samples })
+    }
+
+    // Drops all filter state, for a newly opened capture stream.
+    pub fn reset(&mut self) {
+        if self.dc_filter.is_some() {
+            self.dc_filter = Some(DcFilter::new(self.sample_rate));
+        }
+        if let Some(gate) = self.gate.as_mut() {
+            gate.reset();
+        }
+        self.clipped_samples = 0;
+    }
+}
+
+// Applies fixed gain and hard-limits to full scale, reporting how many samples
+// reached the clipping level (either in the input or because of the gain).
+pub fn apply_gain(samples: &mut [f32], gain_db: f32) -> Option<ClippingReport> {
+    let gain = db_to_gain(gain_db);
+    let mut clipped_samples = 0;
+    for sample in samples.iter_mut() {
+        let value = *sample * gain;
+        if value.abs() >= CLIPPING_LEVEL {
+            clipped_samples += 1;
+        }
+        *sample = value.clamp(-1.0, 1.0);
+    }
+    (clipped_samples > 0).then_some(ClippingReport { clipped_samples })
+}
+
+// Scales the recording towards a peak or RMS target and returns the applied
+// gain in dB. RMS normalization never pushes the peak above the peak target.
+pub fn normalize(samples: &mut [f32], mode: NormalizeMode) -> f32 {
+    let peak = samples
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    if peak <= 0.0 {
+        return 0.0;
+    }
+    let peak_gain = db_to_gain(PEAK_TARGET_DBFS) / peak;
+    let gain = match mode {
+        NormalizeMode::Off => return 0.0,
+        NormalizeMode::Peak => peak_gain,
+        NormalizeMode::Rms => {
+            let rms = (samples.iter().map(|sample| sample * sample).sum::<f32>()
+                / samples.len() as f32)
+                .sqrt();
+            (db_to_gain(RMS_TARGET_DBFS) / rms).min(peak_gain)
+        }
+    }
+    .min(db_to_gain(MAX_NORMALIZE_GAIN_DB));
+    for sample in samples.iter_mut() {
+        *sample *= gain;
+    }
+    20.0 * gain.log10()
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_dc_offset_centers_signal() {
+        let mut samples: Vec<f32> = (0..16_000)
+            .map(|index| 0.3 + 0.1 * (index as f32 * 0.2).sin())
+            .collect();
+        let mut filter = DcFilter::new(16_000);
+        for block in samples.chunks_mut(320) {
+            filter.process(block);
+        }
+        let tail = &samples[8_000..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        assert!(mean.abs() < 0.01, "mean {mean}");
+    }
+
+    #[test]
+    fn apply_gain_reports_clipping() {
+        let mut samples = vec![0.1, 0.6, -0.6];
+        let report = apply_gain(&mut samples, 6.0);
+        assert_eq!(report, Some(ClippingReport { clipped_samples: 2 }));
+        assert_eq!(samples[1], 1.0);
+        assert!((samples[0] - 0.1995).abs() < 1e-3);
+        assert_eq!(apply_gain(&mut [0.1, 0.2], 0.0), None);
+    }
+
+    #[test]
+    fn conditioner_counts_clipped_input_at_unity_gain() {
+        let mut conditioner = InputConditioner::new(16_000, 0.0, false, false);
+        let mut output = Vec::new();
+        conditioner.process(&[0.2, 1.0, -1.0, 0.995, 0.5], &mut output);
+        assert_eq!(output, vec![0.2, 1.0, -1.0, 0.995, 0.5]);
+        assert_eq!(
+            conditioner.take_clipping(),
+            Some(ClippingReport { clipped_samples: 3 })
+        );
+        assert_eq!(conditioner.take_clipping(), None);
+
+        let mut boosted = InputConditioner::new(16_000, 6.0, false, false);
+        boosted.process(&[1.0, 0.6, 0.1], &mut output);
+        assert_eq!(
+            boosted.take_clipping(),
+            Some(ClippingReport { clipped_samples: 2 })
+        );
+    }
+
+    #[test]
+    fn normalize_raises_quiet_recordings_without_clipping() {
+        let mut peak = vec![0.05, -0.1, 0.02];
+        let gain_db = normalize(&mut peak, NormalizeMode::Peak);
+        assert!((gain_db - 19.0).abs() < 0.01);
+        assert!((peak[1] + 0.891).abs() < 1e-3);
+
+        let mut rms = vec![0.01; 100];
+        rms[0] = 0.5;
+        normalize(&mut rms, NormalizeMode::Rms);
+        assert!(rms.iter().all(|sample| sample.abs() <= 0.892));
+
+        let mut silence = vec![0.0; 10];
+        assert_eq!(normalize(&mut silence, NormalizeMode::Rms), 0.0);
+    }
+}
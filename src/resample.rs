@@ -0,0 +1,145 @@
+use std::f64::consts::PI;
+
+use crate::types::ResampleQuality;
+
+// Zero crossings of the sinc kernel on each side of the output sample.
+const SINC_ZERO_CROSSINGS: usize = 16;
+
+// Streaming sample-rate converter for mono audio. Input can be fed in
+// arbitrary chunk sizes; output is produced as soon as enough input is
+// buffered to evaluate the kernel.
+pub struct Resampler {
+    quality: ResampleQuality,
+    // Input samples per output sample.
+    step: f64,
+    // Low-pass cutoff relative to the input Nyquist frequency.
+    cutoff: f64,
+    half_taps: usize,
+    pending: Vec<f32>,
+    position: f64,
+}
+
+impl Resampler {
+    pub fn new(input_rate: u32, output_rate: u32, quality: ResampleQuality) -> Self {
+        let step = input_rate as f64 / output_rate as f64;
+        let cutoff = (output_rate as f64 / input_rate as f64).min(1.0);
+        let half_taps = match quality {
+            ResampleQuality::Linear => 1,
+            ResampleQuality::Sinc => (SINC_ZERO_CROSSINGS as f64 / cutoff).ceil() as usize,
+        };
+        Self {
+            quality,
+            step,
+            cutoff,
+            half_taps,
+            // Leading zeros let the first output sample sit on the first input sample.
+            pending: vec![0.0; half_taps],
+            position: half_taps as f64,
+        }
+    }
+
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        self.pending.extend_from_slice(input);
+        loop {
+            let index = self.position.floor() as usize;
+            if index + self.half_taps >= self.pending.len() {
+                break;
+            }
+            let fraction = self.position - index as f64;
+            let sample = match self.quality {
+                ResampleQuality::Linear => {
+                    let current = self.pending[index] as f64;
+                    let next = self.pending[index + 1] as f64;
+                    current + (next - current) * fraction
+                }
+                ResampleQuality::Sinc => self.sinc_sample(index, fraction),
+            };
+            output.push(sample as f32);
+            self.position += self.step;
+        }
+
+        let consumed = (self.position.floor() as usize).saturating_sub(self.half_taps);
+        if consumed > 0 {
+            self.pending.drain(..consumed);
+            self.position -= consumed as f64;
+        }
+    }
+
+    fn sinc_sample(&self, index: usize, fraction: f64) -> f64 {
+        let half = self.half_taps as f64;
+        let first = index + 1 - self.half_taps;
+        let mut sum = 0.0;
+        for tap in first..=index + self.half_taps {
+            let distance = (index as f64 + fraction) - tap as f64;
+            if distance.abs() >= half {
+                continue;
+            }
+            sum += self.pending[tap] as f64 * self.kernel(distance, half);
+        }
+        sum
+    }
+
+    // Blackman-windowed sinc scaled by the cutoff so DC gain stays at one.
+    fn kernel(&self, distance: f64, half: f64) -> f64 {
+        let x = distance * self.cutoff;
+        let sinc = if x.abs() < 1e-9 {
+            1.0
+        } else {
+            (PI * x).sin() / (PI * x)
+        };
+        let phase = PI * (distance / half + 1.0);
+        let window = 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+        self.cutoff * sinc * window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(rate: u32, frequency: f64, samples: usize) -> Vec<f32> {
+        (0..samples)
+            .map(|n| (2.0 * PI * frequency * n as f64 / rate as f64).sin() as f32 * 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn output_length_follows_rate_ratio() {
+        for quality in [ResampleQuality::Linear, ResampleQuality::Sinc] {
+            let mut resampler = Resampler::new(48_000, 16_000, quality);
+            let mut output = Vec::new();
+            for chunk in vec![0.1f32; 48_000].chunks(480) {
+                resampler.process(chunk, &mut output);
+            }
+            let expected = 16_000.0;
+            assert!((output.len() as f64 - expected).abs() < 40.0, "{quality:?}");
+        }
+    }
+
+    #[test]
+    fn sinc_preserves_in_band_tone() {
+        let mut resampler = Resampler::new(44_100, 16_000, ResampleQuality::Sinc);
+        let mut output = Vec::new();
+        resampler.process(&sine(44_100, 440.0, 44_100), &mut output);
+
+        let expected = sine(16_000, 440.0, output.len());
+        let settled = 200..output.len() - 200;
+        let max_error = settled
+            .map(|index| (output[index] - expected[index]).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_error < 0.01, "max error {max_error}");
+    }
+
+    #[test]
+    fn sinc_attenuates_tone_above_output_nyquist() {
+        let mut resampler = Resampler::new(48_000, 16_000, ResampleQuality::Sinc);
+        let mut output = Vec::new();
+        resampler.process(&sine(48_000, 12_000.0, 48_000), &mut output);
+
+        let settled = &output[200..output.len() - 200];
+        let peak = settled
+            .iter()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        assert!(peak < 0.01, "peak {peak}");
+    }
+}
@@ -0,0 +1,943 @@
+use ringbuf::HeapConsumer;
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::process::{Child, ChildStderr, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::audio::{self, AudioError, AudioErrorKind, ChannelMixer, InputDeviceInfo};
+use crate::daemon::{AudioBackend, CaptureSource};
+use crate::resample::Resampler;
+use crate::types::{ChannelSelection, ResampleQuality};
+
+// How long a recorder may take to link to its source and deliver audio.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Sound-server sources are listed as "Description [node.name]" so users can
+// configure either the human-readable description or the node name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceInfo {
+    pub name: String,
+    pub description: String,
+    pub monitor_of: Option<String>,
+    pub channels: Option<u16>,
+    pub sample_rate: Option<u32>,
+    pub sample_format: Option<String>,
+    pub is_default: bool,
+}
+
+impl SourceInfo {
+    pub fn label(&self) -> String {
+        format!("{} [{}]", self.description, self.name)
+    }
+
+    pub fn matches(&self, requested: &str) -> bool {
+        self.name.eq_ignore_ascii_case(requested)
+            || self.description.eq_ignore_ascii_case(requested)
+            || self.label().eq_ignore_ascii_case(requested)
+    }
+}
+
+// Capture through the `pw-record` / `parec` command-line clients rather than
+// a native client library. Known sources are recorded in their own format and
+// then mixed and resampled here, like the cpal backend.
+pub struct PipeWireBackend {
+    pub resample_quality: ResampleQuality,
+    pub channel: ChannelSelection,
+}
+
+pub struct PulseBackend {
+    pub resample_quality: ResampleQuality,
+    pub channel: ChannelSelection,
+}
+
+impl AudioBackend for PipeWireBackend {
+    fn list_input_devices(&self, _host: &cpal::Host) -> Result<Vec<String>, AudioError> {
+        labels(list_pipewire_sources()?)
+    }
+
+    fn describe_input_devices(
+        &self,
+        _host: &cpal::Host,
+    ) -> Result<Vec<InputDeviceInfo>, AudioError> {
+        describe(list_pipewire_sources()?)
+    }
+
+    fn device_exists(&self, _host: &cpal::Host, devices: &[String], requested: &str) -> bool {
+        match_label("pipewire", devices, requested).is_ok()
+    }
+
+    fn start_capture(
+        &self,
+        _host: &cpal::Host,
+        device_name: Option<&str>,
+        sample_rate: u32,
+    ) -> Result<Box<dyn CaptureSource>, AudioError> {
+        let source = device_name
+            .map(|requested| find_source("pipewire", list_pipewire_sources()?, requested))
+            .transpose()?;
+        let format = StreamFormat::new(
+            source.as_ref(),
+            sample_rate,
+            self.channel,
+            self.resample_quality,
+        );
+        let mut command = Command::new("pw-record");
+        command
+            .arg("--raw")
+            .arg(format!("--rate={}", format.native_rate))
+            .arg(format!("--channels={}", format.channels))
+            .arg("--format=f32");
+        if let Some(source) = &source {
+            match &source.monitor_of {
+                Some(sink) => {
+                    command
+                        .arg(format!("--target={sink}"))
+                        .arg("-P")
+                        .arg("{ stream.capture.sink=true }");
+                }
+                None => {
+                    command.arg(format!("--target={}", source.name));
+                }
+            }
+        }
+        command.arg("-");
+        Ok(Box::new(ProcessCapture::spawn(
+            command,
+            "pw-record",
+            source.as_ref(),
+            format,
+        )?))
+    }
+}
+
+impl AudioBackend for PulseBackend {
+    fn list_input_devices(&self, _host: &cpal::Host) -> Result<Vec<String>, AudioError> {
+        labels(list_pulse_sources()?)
+    }
+
+    fn describe_input_devices(
+        &self,
+        _host: &cpal::Host,
+    ) -> Result<Vec<InputDeviceInfo>, AudioError> {
+        describe(list_pulse_sources()?)
+    }
+
+    fn device_exists(&self, _host: &cpal::Host, devices: &[String], requested: &str) -> bool {
+        match_label("pulse", devices, requested).is_ok()
+    }
+
+    fn start_capture(
+        &self,
+        _host: &cpal::Host,
+        device_name: Option<&str>,
+        sample_rate: u32,
+    ) -> Result<Box<dyn CaptureSource>, AudioError> {
+        let source = device_name
+            .map(|requested| find_source("pulse", list_pulse_sources()?, requested))
+            .transpose()?;
+        let format = StreamFormat::new(
+            source.as_ref(),
+            sample_rate,
+            self.channel,
+            self.resample_quality,
+        );
+        let mut command = Command::new("parec");
+        command
+            .arg("--raw")
+            .arg("--format=float32le")
+            .arg(format!("--rate={}", format.native_rate))
+            .arg(format!("--channels={}", format.channels));
+        if let Some(source) = &source {
+            command.arg(format!("--device={}", source.name));
+        }
+        Ok(Box::new(ProcessCapture::spawn(
+            command,
+            "parec",
+            source.as_ref(),
+            format,
+        )?))
+    }
+}
+
+fn label_matches(label: &str, requested: &str) -> bool {
+    if label.eq_ignore_ascii_case(requested) {
+        return true;
+    }
+    label
+        .strip_suffix(']')
+        .and_then(|label| label.rsplit_once(" ["))
+        .is_some_and(|(description, name)| {
+            description.eq_ignore_ascii_case(requested) || name.eq_ignore_ascii_case(requested)
+        })
+}
+
+fn match_label(host: &str, labels: &[String], requested: &str) -> Result<usize, AudioError> {
+    match labels
+        .iter()
+        .position(|label| label_matches(label, requested))
+    {
+        Some(index) => Ok(index),
+        None => audio::find_device(host, labels, requested),
+    }
+}
+
+// Node names are what the sound server itself uses to route streams, so they
+// double as stable ids. Sample formats are converted by the server.
+fn describe(sources: Vec<SourceInfo>) -> Result<Vec<InputDeviceInfo>, AudioError> {
+    if sources.is_empty() {
+        return Err(AudioError::new(
+            AudioErrorKind::DeviceUnavailable,
+            "no input devices available",
+        ));
+    }
+    Ok(sources
+        .iter()
+        .map(|source| InputDeviceInfo {
+            id: source.name.clone(),
+            is_default: source.is_default,
+            sample_rates: source.sample_rate.into_iter().collect(),
+            channels: source.channels.into_iter().collect(),
+            sample_formats: source.sample_format.iter().cloned().collect(),
+            ..InputDeviceInfo::named(source.label())
+        })
+        .collect())
+}
+
+fn labels(sources: Vec<SourceInfo>) -> Result<Vec<String>, AudioError> {
+    if sources.is_empty() {
+        return Err(AudioError::new(
+            AudioErrorKind::DeviceUnavailable,
+            "no input devices available",
+        ));
+    }
+    Ok(sources.iter().map(SourceInfo::label).collect())
+}
+
+fn find_source(
+    host: &str,
+    mut sources: Vec<SourceInfo>,
+    requested: &str,
+) -> Result<SourceInfo, AudioError> {
+    if let Some(index) = sources.iter().position(|source| source.matches(requested)) {
+        return Ok(sources.swap_remove(index));
+    }
+    let labels: Vec<String> = sources.iter().map(SourceInfo::label).collect();
+    let index = audio::find_device(host, &labels, requested)?;
+    Ok(sources.swap_remove(index))
+}
+
+fn list_pipewire_sources() -> Result<Vec<SourceInfo>, AudioError> {
+    let dump = run_listing(Command::new("pw-dump"), "pw-dump")?;
+    parse_pw_dump(&dump)
+}
+
+fn list_pulse_sources() -> Result<Vec<SourceInfo>, AudioError> {
+    let mut command = Command::new("pactl");
+    command.args(["list", "sources"]);
+    let listing = run_listing(command, "pactl")?;
+    let mut sources = parse_pactl_sources(&listing);
+    // The default marker is informational; listing still works without it.
+    let mut command = Command::new("pactl");
+    command.arg("info");
+    if let Some(default) = run_listing(command, "pactl")
+        .ok()
+        .and_then(|info| parse_pactl_default_source(&info))
+    {
+        mark_default(&mut sources, &default);
+    }
+    Ok(sources)
+}
+
+fn mark_default(sources: &mut [SourceInfo], default: &str) {
+    for source in sources {
+        source.is_default = source.name == default;
+    }
+}
+
+fn run_listing(mut command: Command, program: &str) -> Result<String, AudioError> {
+    // pactl translates its field names; the parsers expect the C locale.
+    let output = command
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| spawn_error(program, err))?;
+    if !output.status.success() {
+        return Err(AudioError::new(
+            AudioErrorKind::DeviceQuery,
+            with_reason(
+                format!("{program} exited with {}", output.status),
+                last_line(&String::from_utf8_lossy(&output.stderr)),
+            ),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// The client tools each host shells out to, and where distributions ship them.
+fn required_tools(program: &str) -> Option<(&'static str, &'static str)> {
+    match program {
+        "pw-record" | "pw-dump" => Some((
+            "pipewire",
+            "pw-record and pw-dump (pipewire-bin on Debian/Ubuntu, pipewire on Arch/Fedora)",
+        )),
+        "parec" | "pactl" => Some((
+            "pulse",
+            "parec and pactl (pulseaudio-utils on Debian/Ubuntu, libpulse on Arch/Fedora)",
+        )),
+        _ => None,
+    }
+}
+
+fn spawn_error(program: &str, err: std::io::Error) -> AudioError {
+    if err.kind() == ErrorKind::NotFound {
+        let message = match required_tools(program) {
+            Some((host, tools)) => {
+                format!("{program} not found; audio_host = \"{host}\" needs {tools}")
+            }
+            None => format!("{program} not found"),
+        };
+        return AudioError::new(AudioErrorKind::DeviceUnavailable, message);
+    }
+    AudioError::new(
+        AudioErrorKind::DeviceQuery,
+        format!("failed to run {program}: {err}"),
+    )
+}
+
+pub fn parse_pw_dump(dump: &str) -> Result<Vec<SourceInfo>, AudioError> {
+    let objects: Vec<serde_json::Value> = serde_json::from_str(dump).map_err(|err| {
+        AudioError::new(
+            AudioErrorKind::DeviceQuery,
+            format!("failed to parse pw-dump output: {err}"),
+        )
+    })?;
+
+    let mut sources = Vec::new();
+    let mut default = None;
+    for object in &objects {
+        if object["type"] == "PipeWire:Interface:Metadata"
+            && object["props"]["metadata.name"] == "default"
+        {
+            default = default.or_else(|| pw_default_source(&object["metadata"]));
+            continue;
+        }
+        if object["type"] != "PipeWire:Interface:Node" {
+            continue;
+        }
+        let props = &object["info"]["props"];
+        let Some(name) = props["node.name"].as_str() else {
+            continue;
+        };
+        let description = props["node.description"]
+            .as_str()
+            .or_else(|| props["node.nick"].as_str())
+            .unwrap_or(name);
+        let channels = props["audio.channels"]
+            .as_u64()
+            .and_then(|channels| u16::try_from(channels).ok());
+        let sample_rate = props["audio.rate"]
+            .as_u64()
+            .and_then(|rate| u32::try_from(rate).ok());
+        let sample_format = props["audio.format"].as_str().map(str::to_lowercase);
+        match props["media.class"].as_str() {
+            Some("Audio/Source") | Some("Audio/Source/Virtual") => sources.push(SourceInfo {
+                name: name.to_string(),
+                description: description.to_string(),
+                monitor_of: None,
+                channels,
+                sample_rate,
+                sample_format: sample_format.clone(),
+                is_default: false,
+            }),
+            Some("Audio/Sink") => sources.push(SourceInfo {
+                name: format!("{name}.monitor"),
+                description: format!("Monitor of {description}"),
+                monitor_of: Some(name.to_string()),
+                channels,
+                sample_rate,
+                sample_format,
+                is_default: false,
+            }),
+            _ => {}
+        }
+    }
+    if let Some(default) = default {
+        mark_default(&mut sources, &default);
+    }
+    Ok(sources)
+}
+
+// The `default` metadata names the default source as `{"name": "<node.name>"}`,
+// which older pw-dump versions print as an encoded JSON string.
+fn pw_default_source(metadata: &serde_json::Value) -> Option<String> {
+    let entry = metadata
+        .as_array()?
+        .iter()
+        .find(|entry| entry["key"] == "default.audio.source")?;
+    let value = match &entry["value"] {
+        serde_json::Value::String(encoded) => serde_json::from_str(encoded).ok()?,
+        value => value.clone(),
+    };
+    value["name"].as_str().map(str::to_string)
+}
+
+pub fn parse_pactl_default_source(info: &str) -> Option<String> {
+    info.lines()
+        .find_map(|line| line.trim().strip_prefix("Default Source: "))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+pub fn parse_pactl_sources(listing: &str) -> Vec<SourceInfo> {
+    let mut sources: Vec<SourceInfo> = Vec::new();
+    let mut name: Option<String> = None;
+    for line in listing.lines() {
+        let line = line.trim();
+        if line.starts_with("Source #") {
+            name = None;
+        } else if let Some(value) = line.strip_prefix("Name: ") {
+            name = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("Description: ") {
+            if let Some(name) = name.take() {
+                sources.push(SourceInfo {
+                    name,
+                    description: value.trim().to_string(),
+                    monitor_of: None,
+                    channels: None,
+                    sample_rate: None,
+                    sample_format: None,
+                    is_default: false,
+                });
+            }
+        } else if let Some(value) = line.strip_prefix("Sample Specification: ") {
+            // e.g. `s16le 2ch 44100Hz`; pactl prints it after the description.
+            if let Some(source) = sources.last_mut() {
+                source.sample_format = value.split_whitespace().next().map(str::to_string);
+                for field in value.split_whitespace() {
+                    if let Some(channels) = field.strip_suffix("ch") {
+                        source.channels = channels.parse().ok();
+                    } else if let Some(rate) = field.strip_suffix("Hz") {
+                        source.sample_rate = rate.parse().ok();
+                    }
+                }
+            }
+        }
+    }
+    sources
+}
+
+// What the recorder is asked to produce. A source with a known format is
+// recorded as-is and mixed and resampled here; otherwise the server converts to
+// mono at the daemon rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StreamFormat {
+    native_rate: u32,
+    channels: u16,
+    sample_rate: u32,
+    selection: ChannelSelection,
+    quality: ResampleQuality,
+}
+
+impl StreamFormat {
+    fn new(
+        source: Option<&SourceInfo>,
+        sample_rate: u32,
+        selection: ChannelSelection,
+        quality: ResampleQuality,
+    ) -> Self {
+        let channels = match (selection, source.and_then(|source| source.channels)) {
+            (ChannelSelection::Average, _) | (_, None) => 1,
+            (_, Some(channels)) => channels.max(1),
+        };
+        Self {
+            native_rate: source
+                .and_then(|source| source.sample_rate)
+                .unwrap_or(sample_rate),
+            channels,
+            sample_rate,
+            selection: if channels == 1 {
+                ChannelSelection::Average
+            } else {
+                selection
+            },
+            quality,
+        }
+    }
+}
+
+// Runs a recorder that writes raw f32le frames to stdout. A reader thread
+// mixes them into a bounded ring, counting what it has to drop when the
+// daemon falls behind.
+struct ProcessCapture {
+    child: Child,
+    program: String,
+    consumer: HeapConsumer<f32>,
+    overflow_count: Arc<AtomicUsize>,
+    native_rate: u32,
+    sample_rate: u32,
+    resampler: Option<Resampler>,
+    native: Vec<f32>,
+    device: Option<String>,
+    details: Vec<String>,
+    stderr: Option<thread::JoinHandle<Option<String>>>,
+}
+
+impl ProcessCapture {
+    fn spawn(
+        mut command: Command,
+        program: &str,
+        source: Option<&SourceInfo>,
+        format: StreamFormat,
+    ) -> Result<Self, AudioError> {
+        let mixer = ChannelMixer::new(format.selection, format.channels)?;
+        let device = source.map(SourceInfo::label);
+        let mut details = vec![format!(
+            "Selected input device: {}",
+            device.as_deref().unwrap_or("default")
+        )];
+        details.push(format!(
+            "Input stream: {program}, {} ch, {} Hz",
+            format.channels, format.native_rate
+        ));
+        if format.channels > 1 {
+            details.push(format!("Input channel: {}", format.selection));
+        }
+        let resampler = (format.native_rate != format.sample_rate).then(|| {
+            details.push(format!(
+                "Resampling {} Hz -> {} Hz ({:?})",
+                format.native_rate, format.sample_rate, format.quality
+            ));
+            Resampler::new(format.native_rate, format.sample_rate, format.quality)
+        });
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| spawn_error(program, err))?;
+        let mut stderr = child.stderr.take().map(read_stderr_tail);
+        let Some(mut stdout) = child.stdout.take() else {
+            stop_child(&mut child);
+            return Err(AudioError::new(
+                AudioErrorKind::StreamBuild,
+                format!("{program} stdout unavailable"),
+            ));
+        };
+
+        let (producer, consumer) = audio::capture_ring(format.native_rate).split();
+        let overflow_count = Arc::new(AtomicUsize::new(0));
+        let mut writer = audio::CaptureWriter::new(producer, mixer, Arc::clone(&overflow_count));
+        let frame_samples = usize::from(format.channels);
+        let (started, first_bytes) = mpsc::sync_channel(1);
+        thread::spawn(move || {
+            let mut decoder = F32Decoder::default();
+            let mut pending = Vec::new();
+            let mut bytes = [0u8; 4096];
+            loop {
+                match stdout.read(&mut bytes) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => {
+                        let _ = started.try_send(());
+                        pending.extend(decoder.decode(&bytes[..read]));
+                        let complete = pending.len() / frame_samples * frame_samples;
+                        writer.write(&pending[..complete]);
+                        pending.drain(..complete);
+                    }
+                }
+            }
+        });
+
+        // Wait for audio to flow; recorders exit right away on a bad target.
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            match first_bytes.recv_timeout(Duration::from_millis(10)) {
+                Ok(()) => break,
+                Err(RecvTimeoutError::Timeout) if Instant::now() < deadline => {
+                    if let Ok(Some(status)) = child.try_wait() {
+                        return Err(startup_exit(program, status, stderr.take()));
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    stop_child(&mut child);
+                    return Err(AudioError::new(
+                        AudioErrorKind::StreamStart,
+                        format!(
+                            "{program} produced no audio within {} s",
+                            STARTUP_TIMEOUT.as_secs()
+                        ),
+                    ));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    stop_child(&mut child);
+                    let status = child.wait().map_err(|err| spawn_error(program, err))?;
+                    return Err(startup_exit(program, status, stderr.take()));
+                }
+            }
+        }
+
+        Ok(Self {
+            child,
+            program: program.to_string(),
+            consumer,
+            overflow_count,
+            native_rate: format.native_rate,
+            sample_rate: format.sample_rate,
+            resampler,
+            native: Vec::new(),
+            device,
+            details,
+            stderr,
+        })
+    }
+}
+
+fn startup_exit(
+    program: &str,
+    status: std::process::ExitStatus,
+    stderr: Option<thread::JoinHandle<Option<String>>>,
+) -> AudioError {
+    let reason = stderr.and_then(|reader| reader.join().ok().flatten());
+    AudioError::new(
+        AudioErrorKind::StreamStart,
+        with_reason(
+            format!("{program} exited during startup with {status}"),
+            reason,
+        ),
+    )
+}
+
+fn with_reason(message: String, reason: Option<String>) -> String {
+    match reason {
+        Some(reason) => format!("{message}: {reason}"),
+        None => message,
+    }
+}
+
+fn last_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+// Keeps draining the recorder's stderr so it never blocks on a full pipe, and
+// returns the last line it printed, which explains a failed start.
+fn read_stderr_tail(stderr: ChildStderr) -> thread::JoinHandle<Option<String>> {
+    thread::spawn(move || {
+        let mut last = None;
+        for line in BufReader::new(stderr).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(line) = last_line(&line) {
+                last = Some(line);
+            }
+        }
+        last
+    })
+}
+
+fn stop_child(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+impl CaptureSource for ProcessCapture {
+    fn drain(&mut self, output: &mut Vec<f32>) {
+        match self.resampler.as_mut() {
+            Some(resampler) => {
+                self.native.clear();
+                audio::pop_available(&mut self.consumer, &mut self.native);
+                resampler.process(&self.native, output);
+            }
+            None => audio::pop_available(&mut self.consumer, output),
+        }
+    }
+
+    // Recorders exit when their source node disappears.
+    fn device_lost(&mut self) -> Option<String> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => Some(with_reason(
+                format!("{} exited with {status}", self.program),
+                self.stderr
+                    .take()
+                    .and_then(|reader| reader.join().ok().flatten()),
+            )),
+            _ => None,
+        }
+    }
+
+    fn take_overflow(&mut self) -> usize {
+        let dropped = self.overflow_count.swap(0, Ordering::Relaxed);
+        (dropped as u64 * u64::from(self.sample_rate) / u64::from(self.native_rate.max(1))) as usize
+    }
+
+    fn details(&self) -> Vec<String> {
+        self.details.clone()
+    }
+
+    fn device_id(&self) -> Option<String> {
+        self.device.clone()
+    }
+}
+
+impl Drop for ProcessCapture {
+    fn drop(&mut self) {
+        stop_child(&mut self.child);
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct F32Decoder {
+    partial: Vec<u8>,
+}
+
+impl F32Decoder {
+    pub(crate) fn decode(&mut self, bytes: &[u8]) -> Vec<f32> {
+        self.partial.extend_from_slice(bytes);
+        let complete = self.partial.len() / 4 * 4;
+        let samples = self.partial[..complete]
+            .chunks_exact(4)
+            .map(|sample| f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]))
+            .collect();
+        self.partial.drain(..complete);
+        samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pw_dump_sources_and_sink_monitors() {
+        let dump = r#"[
+            {"id": 30, "type": "PipeWire:Interface:Node", "info": {"props": {
+                "node.name": "alsa_input.usb-Blue_Yeti-00.analog-stereo",
+                "node.description": "Yeti Stereo Microphone Analog Stereo",
+                "media.class": "Audio/Source",
+                "audio.channels": 2, "audio.rate": 48000}}},
+            {"id": 31, "type": "PipeWire:Interface:Node", "info": {"props": {
+                "node.name": "alsa_output.pci-0000_00_1f.3.analog-stereo",
+                "node.description": "Built-in Audio Analog Stereo",
+                "media.class": "Audio/Sink"}}},
+            {"id": 32, "type": "PipeWire:Interface:Node", "info": {"props": {
+                "node.name": "Firefox", "media.class": "Stream/Output/Audio"}}},
+            {"id": 33, "type": "PipeWire:Interface:Port", "info": {"props": {}}},
+            {"id": 34, "type": "PipeWire:Interface:Metadata", "props": {"metadata.name": "default"},
+             "metadata": [
+                {"subject": 0, "key": "default.audio.sink", "value": {"name": "alsa_output.pci-0000_00_1f.3.analog-stereo"}},
+                {"subject": 0, "key": "default.audio.source", "value": {"name": "alsa_input.usb-Blue_Yeti-00.analog-stereo"}}
+             ]}
+        ]"#;
+
+        let sources = parse_pw_dump(dump).expect("parse pw-dump");
+
+        assert_eq!(sources.len(), 2);
+        assert_eq!(
+            sources[0].label(),
+            "Yeti Stereo Microphone Analog Stereo [alsa_input.usb-Blue_Yeti-00.analog-stereo]"
+        );
+        assert!(sources[0].matches("yeti stereo microphone analog stereo"));
+        assert_eq!(sources[0].channels, Some(2));
+        assert_eq!(sources[0].sample_rate, Some(48_000));
+        assert!(sources[0].is_default);
+        assert!(!sources[1].is_default);
+        assert_eq!(
+            sources[1].name,
+            "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"
+        );
+        assert_eq!(
+            sources[1].monitor_of.as_deref(),
+            Some("alsa_output.pci-0000_00_1f.3.analog-stereo")
+        );
+    }
+
+    #[test]
+    fn parses_pactl_source_listing() {
+        let listing = "Source #52\n\tState: SUSPENDED\n\tName: alsa_output.pci.analog-stereo.monitor\n\tDescription: Monitor of Built-in Audio\n\tDriver: PipeWire\n\tSample Specification: s16le 2ch 44100Hz\n\nSource #53\n\tName: alsa_input.pci.analog-stereo\n\tDescription: Built-in Audio Analog Stereo\n";
+
+        let sources = parse_pactl_sources(listing);
+
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].description, "Monitor of Built-in Audio");
+        assert_eq!(sources[0].channels, Some(2));
+        assert_eq!(sources[0].sample_rate, Some(44_100));
+        assert_eq!(sources[1].channels, None);
+        assert!(sources[1].matches("alsa_input.pci.analog-stereo"));
+        assert!(label_matches(
+            &sources[1].label(),
+            "Built-in Audio Analog Stereo"
+        ));
+        assert!(!label_matches(&sources[1].label(), "Built-in Audio"));
+
+        let info = "Server Name: PulseAudio (on PipeWire 1.0.5)\nDefault Sink: alsa_output.pci.analog-stereo\nDefault Source: alsa_input.pci.analog-stereo\n";
+        assert_eq!(
+            parse_pactl_default_source(info).as_deref(),
+            Some("alsa_input.pci.analog-stereo")
+        );
+    }
+
+    #[test]
+    fn describes_sound_server_sources_with_their_format() {
+        let mut sources = parse_pactl_sources(
+            "Source #53\n\tName: alsa_input.pci.analog-stereo\n\tDescription: Built-in Audio\n\tSample Specification: s16le 2ch 48000Hz\n\nSource #54\n\tName: virtual\n\tDescription: Virtual\n",
+        );
+        mark_default(&mut sources, "alsa_input.pci.analog-stereo");
+
+        let devices = describe(sources).expect("describe sources");
+
+        assert_eq!(devices[0].id, "alsa_input.pci.analog-stereo");
+        assert!(devices[0].is_default);
+        assert_eq!(devices[0].sample_rates, vec![48_000]);
+        assert_eq!(devices[0].channels, vec![2]);
+        assert_eq!(devices[0].sample_formats, vec!["s16le".to_string()]);
+        assert!(!devices[1].is_default);
+        assert!(devices[1].sample_rates.is_empty());
+    }
+
+    fn source(channels: Option<u16>, sample_rate: Option<u32>) -> SourceInfo {
+        SourceInfo {
+            name: "alsa_input.usb".to_string(),
+            description: "USB Mic".to_string(),
+            monitor_of: None,
+            channels,
+            sample_rate,
+            sample_format: None,
+            is_default: false,
+        }
+    }
+
+    #[test]
+    fn stream_format_records_known_sources_natively() {
+        let stereo = source(Some(2), Some(48_000));
+
+        let picked = StreamFormat::new(
+            Some(&stereo),
+            16_000,
+            ChannelSelection::Index(2),
+            ResampleQuality::Linear,
+        );
+        assert_eq!((picked.native_rate, picked.channels), (48_000, 2));
+        assert_eq!(picked.selection, ChannelSelection::Index(2));
+
+        let averaged = StreamFormat::new(
+            Some(&stereo),
+            16_000,
+            ChannelSelection::Average,
+            ResampleQuality::Linear,
+        );
+        assert_eq!((averaged.native_rate, averaged.channels), (48_000, 1));
+
+        let unknown = StreamFormat::new(
+            None,
+            16_000,
+            ChannelSelection::MaxEnergy,
+            ResampleQuality::Linear,
+        );
+        assert_eq!((unknown.native_rate, unknown.channels), (16_000, 1));
+        assert_eq!(unknown.selection, ChannelSelection::Average);
+    }
+
+    #[test]
+    fn process_capture_counts_samples_dropped_from_a_full_ring() {
+        // 32000 bytes of zeros are 8000 samples; at 100 Hz the ring holds 6000.
+        let mut command = Command::new("head");
+        command.args(["-c", "32000", "/dev/zero"]);
+        let format = StreamFormat::new(
+            None,
+            100,
+            ChannelSelection::Average,
+            ResampleQuality::Linear,
+        );
+        let mut capture =
+            ProcessCapture::spawn(command, "head", None, format).expect("start capture");
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let mut dropped = 0;
+        while dropped < 2_000 && Instant::now() < deadline {
+            dropped += capture.take_overflow();
+            thread::sleep(Duration::from_millis(5));
+        }
+        let mut samples = Vec::new();
+        capture.drain(&mut samples);
+
+        assert_eq!(dropped, 2_000);
+        assert_eq!(samples.len(), 6_000);
+        assert_eq!(
+            capture.details(),
+            vec![
+                "Selected input device: default".to_string(),
+                "Input stream: head, 1 ch, 100 Hz".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn process_capture_reports_recorder_exit_during_startup() {
+        let err = match ProcessCapture::spawn(
+            Command::new("false"),
+            "false",
+            None,
+            StreamFormat::new(
+                None,
+                16_000,
+                ChannelSelection::Average,
+                ResampleQuality::Linear,
+            ),
+        ) {
+            Ok(_) => panic!("expected startup error"),
+            Err(err) => err,
+        };
+
+        assert_eq!(err.kind, AudioErrorKind::StreamStart);
+        assert!(err.message.starts_with("false exited during startup"));
+    }
+
+    #[test]
+    fn recorder_failures_explain_themselves() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo 'target node not found' >&2; exit 1"]);
+        let err = match ProcessCapture::spawn(
+            command,
+            "pw-record",
+            None,
+            StreamFormat::new(
+                None,
+                16_000,
+                ChannelSelection::Average,
+                ResampleQuality::Linear,
+            ),
+        ) {
+            Ok(_) => panic!("expected startup error"),
+            Err(err) => err,
+        };
+        assert!(
+            err.message.ends_with(": target node not found"),
+            "{}",
+            err.message
+        );
+
+        let missing = spawn_error("parec", std::io::Error::from(ErrorKind::NotFound));
+        assert_eq!(missing.kind, AudioErrorKind::DeviceUnavailable);
+        assert!(missing
+            .message
+            .contains("audio_host = \"pulse\" needs parec and pactl"));
+    }
+
+    #[test]
+    fn decoder_carries_partial_samples_between_reads() {
+        let mut decoder = F32Decoder::default();
+        let bytes: Vec<u8> = [0.5f32, -0.25]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+
+        assert_eq!(decoder.decode(&bytes[..3]), Vec::<f32>::new());
+        assert_eq!(decoder.decode(&bytes[3..]), vec![0.5, -0.25]);
+    }
+}
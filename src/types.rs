@@ -1,5 +1,7 @@
 use clap::ValueEnum;
 use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, ValueEnum, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -20,6 +22,9 @@ pub enum OutputMode {
 pub enum AudioHost {
     Default,
     Alsa,
+    Pipewire,
+    Pulse,
+    File,
 }
 
 impl AudioHost {
@@ -58,3 +63,140 @@ impl VadSetting {
         }
     }
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VadBackend {
+    Energy,
+    Silero,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    Linear,
+    Sinc,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizeMode {
+    Off,
+    Peak,
+    Rms,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceFallback {
+    Default,
+    Off,
+}
+
+// Which input channel(s) feed the mono capture buffer. Channel indices are
+// 1-based to match the labels on audio interfaces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "ChannelSetting")]
+pub enum ChannelSelection {
+    Average,
+    MaxEnergy,
+    Index(u16),
+}
+
+impl FromStr for ChannelSelection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "average" => Ok(Self::Average),
+            "max-energy" => Ok(Self::MaxEnergy),
+            other => match other.parse::<u16>() {
+                Ok(0) => Err("channel index starts at 1".to_string()),
+                Ok(index) => Ok(Self::Index(index)),
+                Err(_) => Err(format!(
+                    "invalid channel '{value}': expected average, max-energy or a channel number"
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ChannelSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Average => write!(f, "average"),
+            Self::MaxEnergy => write!(f, "max-energy"),
+            Self::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChannelSetting {
+    Index(u16),
+    Name(String),
+}
+
+impl TryFrom<ChannelSetting> for ChannelSelection {
+    type Error = String;
+
+    fn try_from(setting: ChannelSetting) -> Result<Self, Self::Error> {
+        match setting {
+            ChannelSetting::Index(index) => index.to_string().parse(),
+            ChannelSetting::Name(name) => name.parse(),
+        }
+    }
+}
+
+// Which GPU whisper.cpp runs on: the first supported device, none (CPU only),
+// or a device index as numbered by the GPU backend.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "GpuSetting")]
+pub enum GpuSelection {
+    Auto,
+    Off,
+    Device(u32),
+}
+
+impl FromStr for GpuSelection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "off" => Ok(Self::Off),
+            other => other.parse::<u32>().map(Self::Device).map_err(|_| {
+                format!("invalid gpu '{value}': expected auto, off or a GPU device index")
+            }),
+        }
+    }
+}
+
+impl fmt::Display for GpuSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Off => write!(f, "off"),
+            Self::Device(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GpuSetting {
+    Index(u32),
+    Name(String),
+}
+
+impl TryFrom<GpuSetting> for GpuSelection {
+    type Error = String;
+
+    fn try_from(setting: GpuSetting) -> Result<Self, Self::Error> {
+        match setting {
+            GpuSetting::Index(index) => Ok(Self::Device(index)),
+            GpuSetting::Name(name) => name.parse(),
+        }
+    }
+}
@@ -24,8 +24,13 @@ pub enum WhisperError {
     InvalidPath(NulError),
     InitFailed,
     TranscriptionFailed(i32),
+    VadInitFailed,
+    VadFailed,
 }
 
+// Silero VAD in whisper.cpp emits one speech probability per 512-sample window.
+pub const VAD_FRAME_SAMPLES: usize = 512;
+
 struct LogCapture {
     gpu_backend: Mutex<Option<String>>,
     saw_no_gpu: AtomicBool,
@@ -62,6 +67,14 @@ impl LogCapture {
         }
     }
 
+    fn backend(&self) -> String {
+        self.gpu_backend
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .unwrap_or_else(|| "cpu".to_string())
+    }
+
     fn summary(&self) -> String {
         let backend = self.gpu_backend.lock().ok().and_then(|value| value.clone());
         if let Some(backend) = backend {
@@ -108,6 +121,8 @@ impl std::fmt::Display for WhisperError {
             WhisperError::TranscriptionFailed(code) => {
                 write!(f, "whisper transcription failed with code {code}")
             }
+            WhisperError::VadInitFailed => write!(f, "failed to initialize VAD model"),
+            WhisperError::VadFailed => write!(f, "VAD speech detection failed"),
         }
     }
 }
@@ -121,12 +136,64 @@ impl std::error::Error for WhisperError {
     }
 }
 
+// Runtime options for `whisper_init_from_file_with_params`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ContextOptions {
+    pub use_gpu: bool,
+    pub gpu_device: i32,
+    pub flash_attn: bool,
+}
+
+impl Default for ContextOptions {
+    fn default() -> Self {
+        Self {
+            use_gpu: true,
+            gpu_device: 0,
+            flash_attn: false,
+        }
+    }
+}
+
+// The GPU whisper.cpp would use for `gpu_device`, read from ggml's backend
+// registry without loading a model. Counts GPU devices in registry order, like
+// whisper's own device selection.
+pub fn gpu_device_name(gpu_device: i32) -> Option<String> {
+    let index = usize::try_from(gpu_device).ok()?;
+    let count = unsafe { ggml_backend_dev_count() };
+    let device = (0..count)
+        .map(|position| unsafe { ggml_backend_dev_get(position) })
+        .filter(|device| !device.is_null())
+        .filter(|device| {
+            let kind = unsafe { ggml_backend_dev_type(*device) };
+            kind != ggml_backend_dev_type_GGML_BACKEND_DEVICE_TYPE_CPU
+                && kind != ggml_backend_dev_type_GGML_BACKEND_DEVICE_TYPE_ACCEL
+        })
+        .nth(index)?;
+    let name = unsafe { ggml_backend_dev_name(device) };
+    if name.is_null() {
+        return None;
+    }
+    Some(
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
 pub struct WhisperContext {
     ctx: NonNull<whisper_context>,
+    backend: String,
 }
 
 impl WhisperContext {
     pub fn from_file(path: &Path) -> Result<Self, WhisperError> {
+        Self::from_file_with_options(path, ContextOptions::default())
+    }
+
+    pub fn from_file_with_options(
+        path: &Path,
+        options: ContextOptions,
+    ) -> Result<Self, WhisperError> {
         let path_c =
             CString::new(path.as_os_str().as_bytes()).map_err(WhisperError::InvalidPath)?;
         let log_capture = log_capture();
@@ -138,14 +205,25 @@ impl WhisperContext {
             );
         }
         let mut params = unsafe { whisper_context_default_params() };
-        params.use_gpu = true;
-        params.flash_attn = false;
-        params.gpu_device = 0;
+        params.use_gpu = options.use_gpu;
+        params.flash_attn = options.flash_attn;
+        params.gpu_device = options.gpu_device;
 
         let ctx = unsafe { whisper_init_from_file_with_params(path_c.as_ptr(), params) };
         let ctx = NonNull::new(ctx).ok_or(WhisperError::InitFailed)?;
-        eprintln!("{}", log_capture.summary());
-        Ok(Self { ctx })
+        let backend = if options.use_gpu {
+            eprintln!("{}", log_capture.summary());
+            log_capture.backend()
+        } else {
+            eprintln!("whisper: GPU disabled by config; using CPU");
+            "cpu".to_string()
+        };
+        Ok(Self { ctx, backend })
+    }
+
+    // The GPU backend whisper.cpp selected for this context, or `cpu`.
+    pub fn backend(&self) -> &str {
+        &self.backend
     }
 
     pub fn transcribe(
@@ -210,3 +288,46 @@ impl Drop for WhisperContext {
         unsafe { whisper_free(self.ctx.as_ptr()) };
     }
 }
+
+pub struct WhisperVadContext {
+    ctx: NonNull<whisper_vad_context>,
+}
+
+impl WhisperVadContext {
+    pub fn from_file(path: &Path) -> Result<Self, WhisperError> {
+        let path_c =
+            CString::new(path.as_os_str().as_bytes()).map_err(WhisperError::InvalidPath)?;
+        let mut params = unsafe { whisper_vad_default_context_params() };
+        params.use_gpu = false;
+        params.n_threads = 1;
+        let ctx = unsafe { whisper_vad_init_from_file_with_params(path_c.as_ptr(), params) };
+        let ctx = NonNull::new(ctx).ok_or(WhisperError::VadInitFailed)?;
+        Ok(Self { ctx })
+    }
+
+    pub fn speech_probabilities(&mut self, samples: &[f32]) -> Result<Vec<f32>, WhisperError> {
+        if samples.is_empty() {
+            return Ok(Vec::new());
+        }
+        let detected = unsafe {
+            whisper_vad_detect_speech(self.ctx.as_ptr(), samples.as_ptr(), samples.len() as i32)
+        };
+        if !detected {
+            return Err(WhisperError::VadFailed);
+        }
+        let count = unsafe { whisper_vad_n_probs(self.ctx.as_ptr()) };
+        let probs = unsafe { whisper_vad_probs(self.ctx.as_ptr()) };
+        if count <= 0 || probs.is_null() {
+            return Ok(Vec::new());
+        }
+        Ok(unsafe { std::slice::from_raw_parts(probs, count as usize) }.to_vec())
+    }
+}
+
+impl Drop for WhisperVadContext {
+    fn drop(&mut self) {
+        unsafe {
+            whisper_vad_free(self.ctx.as_ptr());
+        }
+    }
+}
@@ -1,5 +1,6 @@
 // Automated acceptance tests for docs/acceptance-tests.md.
 // Keep AT-xx mappings in sync with the documentation.
+use std::collections::HashMap;
 use std::env;
 use std::error::Error;
 use std::ffi::{OsStr, OsString};
@@ -34,9 +35,14 @@ use sv::daemon::test_support::{
 #[cfg(feature = "test-support")]
 use sv::daemon::{DaemonConfig, DaemonDeps};
 #[cfg(feature = "test-support")]
+use sv::file_capture::FileBackend;
+#[cfg(feature = "test-support")]
 use sv::model::{ModelSize, ModelVariants};
 #[cfg(feature = "test-support")]
-use sv::types::{AudioHost, OutputFormat, OutputMode, VadMode};
+use sv::types::{
+    AudioHost, ChannelSelection, DeviceFallback, GpuSelection, NormalizeMode, OutputFormat,
+    OutputMode, ResampleQuality, VadBackend, VadMode,
+};
 
 #[test]
 fn at01_daemon_starts_with_valid_model() -> Result<(), Box<dyn Error>> {
@@ -96,7 +102,8 @@ fn at01a_missing_model_is_auto_downloaded() -> Result<(), Box<dyn Error>> {
 
     let spec =
         sv::model::ModelSpec::new(sv::model::ModelSize::Auto, sv::model::ModelLanguage::Auto);
-    let prepared = sv::model::prepare_model(None, &spec, true)?;
+    let source = sv::model::ModelSource::Published(spec);
+    let prepared = sv::model::prepare_model(&source, true)?;
 
     assert!(prepared.downloaded, "expected model download");
     assert!(prepared.path.exists(), "expected model file to exist");
@@ -124,6 +131,78 @@ fn at01b_language_selects_model_variant() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn at01c_model_subcommands_manage_model_files() -> Result<(), Box<dyn Error>> {
+    let data_home = temp_dir("soundvibes-acceptance-data");
+    let mut payload = b"lmgg".to_vec();
+    payload.extend_from_slice(b"soundvibes-test-model");
+    let (base_url, server_handle) = start_test_server(payload.clone())?;
+    let binary = env!("CARGO_BIN_EXE_sv");
+    let run = |args: &[&str]| {
+        Command::new(binary)
+            .args(args)
+            .env("XDG_DATA_HOME", &data_home)
+            .env("SV_MODEL_BASE_URL", &base_url)
+            .output()
+    };
+
+    let pull = run(&["model", "pull", "tiny.en"])?;
+    assert!(pull.status.success(), "pull failed: {pull:?}");
+    let _ = server_handle.join();
+    let model_path = data_home.join("soundvibes/models/ggml-tiny.en.bin");
+    assert_eq!(fs::read(&model_path)?, payload);
+
+    let path = run(&["model", "path", "tiny.en"])?;
+    assert_eq!(
+        String::from_utf8_lossy(&path.stdout).trim(),
+        model_path.display().to_string()
+    );
+
+    let list = String::from_utf8_lossy(&run(&["model", "list"])?.stdout).to_string();
+    assert!(list.contains("ggml-tiny.en.bin"), "list output: {list}");
+    assert!(
+        list.contains("tiny: satisfies model_variants en"),
+        "list output: {list}"
+    );
+
+    let verify = run(&["model", "verify"])?;
+    assert!(verify.status.success(), "verify failed: {verify:?}");
+
+    let remove = run(&["model", "rm", "tiny.en"])?;
+    assert!(remove.status.success(), "rm failed: {remove:?}");
+    assert!(!model_path.exists());
+    let remove_again = run(&["model", "rm", "tiny.en"])?;
+    assert_eq!(remove_again.status.code(), Some(2));
+    Ok(())
+}
+
+#[test]
+fn at01d_interrupted_model_download_resumes() -> Result<(), Box<dyn Error>> {
+    let data_home = temp_dir("soundvibes-acceptance-data");
+    let _data_guard = EnvGuard::set("XDG_DATA_HOME", &data_home);
+    let payload = (0..200_000u32)
+        .map(|value| (value % 251) as u8)
+        .collect::<Vec<_>>();
+    let (base_url, server_handle) = start_resuming_test_server(payload.clone(), 70_000)?;
+    let _url_guard = EnvGuard::set("SV_MODEL_BASE_URL", &base_url);
+
+    let spec = sv::model::ModelSpec::new(sv::model::ModelSize::Base, sv::model::ModelLanguage::En);
+    let source = sv::model::ModelSource::Published(spec);
+    let mut reports = Vec::new();
+    let prepared = sv::model::prepare_model_with_progress(&source, true, &mut |progress| {
+        reports.push(progress)
+    })?;
+    let range = server_handle.join().expect("test server panicked");
+
+    assert_eq!(range.as_deref(), Some("bytes=70000-"));
+    assert_eq!(fs::read(&prepared.path)?, payload);
+    assert!(!prepared.path.with_extension("bin.part").exists());
+    let last = reports.last().expect("download progress reported");
+    assert_eq!(last.downloaded_bytes, 200_000);
+    assert_eq!(last.total_bytes, Some(200_000));
+    Ok(())
+}
+
 #[test]
 fn at02_missing_model_returns_exit_code_2() -> Result<(), Box<dyn Error>> {
     let config_home = temp_dir("soundvibes-acceptance-config");
@@ -169,7 +248,7 @@ fn at03_invalid_input_device_returns_exit_code_3() -> Result<(), Box<dyn Error>>
     let runtime_dir = temp_dir("soundvibes-acceptance-runtime");
     write_config(
         &config_home,
-        "language = \"en\"\ndownload_model = false\ndevice = \"nonexistent\"\n",
+        "language = \"en\"\ndownload_model = false\ndevice = \"nonexistent\"\ndevice_fallback = \"off\"\n",
     )?;
 
     let binary = env!("CARGO_BIN_EXE_sv");
@@ -204,19 +283,45 @@ fn at04_daemon_toggle_captures_and_transcribes() -> Result<(), Box<dyn Error>> {
         transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
     };
     let config = DaemonConfig {
-        model_size: ModelSize::Small,
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
         model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
         download_model: false,
+        verify_model_checksum: false,
         language: "en".to_string(),
         device: None,
         audio_host: AudioHost::Default,
+        device_fallback: DeviceFallback::Default,
+        channel: ChannelSelection::Average,
         sample_rate: 16_000,
         format: OutputFormat::Plain,
         mode: OutputMode::Stdout,
         vad: VadMode::Off,
         vad_silence_ms: 800,
         vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
         vad_chunk_ms: 250,
+        vad_auto_stop: false,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
         debug_audio: false,
         debug_vad: false,
         dump_audio: false,
@@ -266,19 +371,45 @@ fn at05_jsonl_output_formatting() -> Result<(), Box<dyn Error>> {
         transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
     };
     let config = DaemonConfig {
-        model_size: ModelSize::Small,
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
         model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
         download_model: false,
+        verify_model_checksum: false,
         language: "en".to_string(),
         device: None,
         audio_host: AudioHost::Default,
+        device_fallback: DeviceFallback::Default,
+        channel: ChannelSelection::Average,
         sample_rate: 16_000,
         format: OutputFormat::Jsonl,
         mode: OutputMode::Stdout,
         vad: VadMode::Off,
         vad_silence_ms: 800,
         vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
         vad_chunk_ms: 250,
+        vad_auto_stop: false,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
         debug_audio: false,
         debug_vad: false,
         dump_audio: false,
@@ -712,19 +843,45 @@ fn at12_control_socket_toggle_with_language_and_status_response() -> Result<(),
         transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
     };
     let config = DaemonConfig {
-        model_size: ModelSize::Small,
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
         model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
         download_model: false,
+        verify_model_checksum: false,
         language: "en".to_string(),
         device: None,
         audio_host: AudioHost::Default,
+        device_fallback: DeviceFallback::Default,
+        channel: ChannelSelection::Average,
         sample_rate: 16_000,
         format: OutputFormat::Plain,
         mode: OutputMode::Stdout,
         vad: VadMode::Off,
         vad_silence_ms: 800,
         vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
         vad_chunk_ms: 250,
+        vad_auto_stop: false,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
         debug_audio: false,
         debug_vad: false,
         dump_audio: false,
@@ -741,6 +898,12 @@ fn at12_control_socket_toggle_with_language_and_status_response() -> Result<(),
         assert_eq!(status_response.state.as_deref(), Some("recording"));
         assert_eq!(status_response.language.as_deref(), Some("fr"));
 
+        let devices_response = sv::daemon::send_devices_command()?;
+        assert!(devices_response.ok);
+        let devices = devices_response.devices.unwrap_or_default();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "Mic");
+
         let _ = sv::daemon::send_toggle_command(None)?;
         let _ = sv::daemon::send_stop_command()?;
         Ok(())
@@ -790,19 +953,45 @@ fn at13_events_socket_fans_out_to_multiple_clients() -> Result<(), Box<dyn Error
         transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
     };
     let config = DaemonConfig {
-        model_size: ModelSize::Small,
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
         model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
         download_model: false,
+        verify_model_checksum: false,
         language: "en".to_string(),
         device: None,
         audio_host: AudioHost::Default,
+        device_fallback: DeviceFallback::Default,
+        channel: ChannelSelection::Average,
         sample_rate: 16_000,
         format: OutputFormat::Plain,
         mode: OutputMode::Stdout,
         vad: VadMode::Off,
         vad_silence_ms: 800,
         vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
         vad_chunk_ms: 250,
+        vad_auto_stop: false,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
         debug_audio: false,
         debug_vad: false,
         dump_audio: false,
@@ -891,19 +1080,45 @@ fn at14_set_language_switches_active_language_and_transcript_language() -> Resul
         transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hej".to_string()])),
     };
     let config = DaemonConfig {
-        model_size: ModelSize::Small,
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
         model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
         download_model: false,
+        verify_model_checksum: false,
         language: "en".to_string(),
         device: None,
         audio_host: AudioHost::Default,
+        device_fallback: DeviceFallback::Default,
+        channel: ChannelSelection::Average,
         sample_rate: 16_000,
         format: OutputFormat::Plain,
         mode: OutputMode::Stdout,
         vad: VadMode::Off,
         vad_silence_ms: 800,
         vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
         vad_chunk_ms: 250,
+        vad_auto_stop: false,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
         debug_audio: false,
         debug_vad: false,
         dump_audio: false,
@@ -958,6 +1173,230 @@ fn at14_set_language_switches_active_language_and_transcript_language() -> Resul
     Ok(())
 }
 
+#[cfg(feature = "test-support")]
+#[test]
+fn at15_file_input_transcribes_wav_until_silence() -> Result<(), Box<dyn Error>> {
+    let wav_path = temp_dir("soundvibes-acceptance-speech").with_extension("wav");
+    // 48 kHz stereo so the file path also exercises mixing and resampling.
+    write_test_wav(&wav_path, &[(0.3, 0.0), (0.6, 0.3), (0.8, 0.0)])?;
+
+    let deps = DaemonDeps {
+        audio: Box::new(FileBackend::new(
+            ResampleQuality::Sinc,
+            ChannelSelection::Average,
+        )),
+        transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
+    };
+    let config = DaemonConfig {
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
+        model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
+        download_model: false,
+        verify_model_checksum: false,
+        language: "en".to_string(),
+        device: Some(wav_path.to_string_lossy().into_owned()),
+        audio_host: AudioHost::File,
+        device_fallback: DeviceFallback::Off,
+        channel: ChannelSelection::Average,
+        sample_rate: 16_000,
+        format: OutputFormat::Plain,
+        mode: OutputMode::Stdout,
+        vad: VadMode::On,
+        vad_silence_ms: 300,
+        vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
+        vad_chunk_ms: 100,
+        vad_auto_stop: true,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
+        debug_audio: false,
+        debug_vad: false,
+        dump_audio: false,
+    };
+
+    let events = run_file_input(&config, &deps)?;
+    let _ = fs::remove_file(&wav_path);
+
+    assert!(events.iter().any(|event| matches!(
+        event,
+        sv::ipc::DaemonEventType::TranscriptFinal { text, .. } if text == "hello"
+    )));
+    assert!(
+        events.contains(&sv::ipc::DaemonEventType::RecordingStopped {
+            language: "en".to_string(),
+            reason: sv::ipc::StopReason::SilenceTimeout,
+        })
+    );
+    Ok(())
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn at15_file_input_reports_no_speech_for_silent_wav() -> Result<(), Box<dyn Error>> {
+    let wav_path = temp_dir("soundvibes-acceptance-silence").with_extension("wav");
+    write_test_wav(&wav_path, &[(0.8, 0.0)])?;
+
+    let deps = DaemonDeps {
+        audio: Box::new(FileBackend::new(
+            ResampleQuality::Sinc,
+            ChannelSelection::Average,
+        )),
+        transcriber_factory: Box::new(TestTranscriberFactory::new(vec!["hello".to_string()])),
+    };
+    let config = DaemonConfig {
+        model_size: ModelSize::Small.into(),
+        models: HashMap::new(),
+        languages: HashMap::new(),
+        model_variants: ModelVariants::Both,
+        model_idle_unload_ms: 0,
+        model_memory_budget_mb: 0,
+        model_auto_benchmark: false,
+        model_auto_target_rtf: 0.5,
+        gpu: GpuSelection::Auto,
+        flash_attn: false,
+        download_model: false,
+        verify_model_checksum: false,
+        language: "en".to_string(),
+        device: Some(wav_path.to_string_lossy().into_owned()),
+        audio_host: AudioHost::File,
+        device_fallback: DeviceFallback::Off,
+        channel: ChannelSelection::Average,
+        sample_rate: 16_000,
+        format: OutputFormat::Plain,
+        mode: OutputMode::Stdout,
+        vad: VadMode::On,
+        vad_silence_ms: 300,
+        vad_threshold: 0.015,
+        device_thresholds: HashMap::new(),
+        vad_chunk_ms: 100,
+        vad_auto_stop: true,
+        vad_backend: VadBackend::Energy,
+        vad_model: None,
+        vad_probability: 0.5,
+        vad_auto_calibrate: false,
+        vad_calibration_ms: 500,
+        audio_level_interval_ms: 0,
+        warm_capture: false,
+        pre_roll_ms: 300,
+        resample_quality: ResampleQuality::Sinc,
+        input_gain_db: 0.0,
+        normalize: NormalizeMode::Off,
+        dc_filter: false,
+        denoise: false,
+        debug_audio: false,
+        debug_vad: false,
+        dump_audio: false,
+    };
+
+    // Silence never triggers auto-stop; the recording ends with the file.
+    let events = run_file_input(&config, &deps)?;
+    let _ = fs::remove_file(&wav_path);
+
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, sv::ipc::DaemonEventType::NoSpeech { .. })));
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, sv::ipc::DaemonEventType::TranscriptFinal { .. })));
+    assert!(
+        events.contains(&sv::ipc::DaemonEventType::RecordingStopped {
+            language: "en".to_string(),
+            reason: sv::ipc::StopReason::EndOfInput,
+        })
+    );
+    Ok(())
+}
+
+// Toggles recording on, optionally off again after `toggle_off_after`, and
+// returns the events up to `recording_stopped`.
+#[cfg(feature = "test-support")]
+fn run_file_input(
+    config: &DaemonConfig,
+    deps: &DaemonDeps,
+) -> Result<Vec<sv::ipc::DaemonEventType>, Box<dyn Error>> {
+    let (sender, receiver) = control_channel();
+    let control_sender = sender.clone();
+    let (event_sender, event_receiver) = mpsc::channel::<sv::ipc::DaemonEvent>();
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_trigger = Arc::clone(&shutdown);
+    let control_thread = thread::spawn(move || {
+        let _ = control_sender.send(control_message(sv::daemon::ControlEvent::Toggle {
+            language: None,
+        }));
+        let mut events = Vec::new();
+        while let Ok(event) = event_receiver.recv_timeout(Duration::from_secs(5)) {
+            let stopped = matches!(
+                event.event,
+                sv::ipc::DaemonEventType::RecordingStopped { .. }
+            );
+            events.push(event.event);
+            if stopped {
+                break;
+            }
+        }
+        shutdown_trigger.store(true, Ordering::Relaxed);
+        events
+    });
+
+    let mut output = TestOutput::default();
+    let result = sv::daemon::run_daemon_loop(
+        config,
+        deps,
+        &mut output,
+        receiver,
+        shutdown.as_ref(),
+        Some(&event_sender),
+    );
+    let events = control_thread
+        .join()
+        .map_err(|_| "control thread panicked")?;
+    result?;
+    Ok(events)
+}
+
+// Writes 48 kHz stereo 16-bit PCM: a 220 Hz tone of the given amplitude per
+// (seconds, amplitude) segment.
+#[cfg(feature = "test-support")]
+fn write_test_wav(path: &std::path::Path, segments: &[(f32, f32)]) -> Result<(), Box<dyn Error>> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 48_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    let mut index = 0u32;
+    for (seconds, amplitude) in segments {
+        for _ in 0..(seconds * 48_000.0) as u32 {
+            let phase = index as f32 * 220.0 * std::f32::consts::TAU / 48_000.0;
+            let sample = (phase.sin() * amplitude * i16::MAX as f32) as i16;
+            writer.write_sample(sample)?;
+            writer.write_sample(sample)?;
+            index += 1;
+        }
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
 #[cfg(feature = "test-support")]
 fn read_daemon_events(
     stream: &mut UnixStream,
@@ -1175,6 +1614,49 @@ fn start_test_server(payload: Vec<u8>) -> Result<(String, thread::JoinHandle<()>
     Ok((format!("http://{addr}"), handle))
 }
 
+type ResumingTestServer = (String, thread::JoinHandle<Option<String>>);
+
+// Drops the first connection after `cut` bytes, then serves the rest of the
+// payload as a 206 response and returns the Range header it was asked for.
+fn start_resuming_test_server(
+    payload: Vec<u8>,
+    cut: usize,
+) -> Result<ResumingTestServer, Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().ok()?;
+        let mut buffer = [0u8; 1024];
+        let _ = stream.read(&mut buffer);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+            payload.len()
+        );
+        let _ = stream.write_all(response.as_bytes());
+        let _ = stream.write_all(&payload[..cut]);
+        drop(stream);
+
+        let (mut stream, _) = listener.accept().ok()?;
+        let read = stream.read(&mut buffer).ok()?;
+        let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+        let range = request
+            .lines()
+            .find(|line| line.to_ascii_lowercase().starts_with("range:"))
+            .map(|line| line["range:".len()..].trim().to_string());
+        let rest = &payload[cut..];
+        let response = format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {cut}-{}/{}\r\n\r\n",
+            rest.len(),
+            payload.len() - 1,
+            payload.len()
+        );
+        let _ = stream.write_all(response.as_bytes());
+        let _ = stream.write_all(rest);
+        range
+    });
+    Ok((format!("http://{addr}"), handle))
+}
+
 fn write_config(config_home: &std::path::Path, contents: &str) -> Result<(), Box<dyn Error>> {
     let config_path = config_home.join("soundvibes").join("config.toml");
     fs::create_dir_all(config_path.parent().expect("config parent"))?;